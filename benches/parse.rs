@@ -0,0 +1,47 @@
+use advent_2025::parse::{parse, split_parse};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROUNDS: usize = 100;
+
+fn bench<F>(name: &str, mut f: F)
+where
+    F: FnMut() -> u64,
+{
+    let mut best = Duration::MAX;
+
+    for _ in 0..ROUNDS {
+        let start = Instant::now();
+
+        black_box(f());
+
+        best = best.min(start.elapsed());
+    }
+
+    println!("{name:30} {best:?}");
+}
+
+fn main() {
+    let numbers: Vec<String> = (0..100_000u64)
+        .map(|n| (n * 2654435761 % 10_000_000_000).to_string())
+        .collect();
+    let line = numbers.join(",");
+
+    bench("parse::<u64>", || {
+        numbers
+            .iter()
+            .map(|n| parse::<u64>(n.as_bytes()).unwrap())
+            .sum()
+    });
+    bench("str::parse::<u64>", || {
+        numbers.iter().map(|n| n.parse::<u64>().unwrap()).sum()
+    });
+    bench("split_parse::<u64>", || {
+        split_parse::<u64>(line.as_bytes(), b',')
+            .map(Result::unwrap)
+            .sum()
+    });
+    bench("str::split + str::parse::<u64>", || {
+        line.split(',').map(|n| n.parse::<u64>().unwrap()).sum()
+    });
+}
@@ -0,0 +1,85 @@
+use std::ops::Sub;
+use std::time::Duration;
+
+/// CPU time used by the whole process, all threads included.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CpuTime {
+    pub user: Duration,
+    pub system: Duration,
+}
+
+#[cfg(unix)]
+fn timeval_to_duration(time: libc::timeval) -> Duration {
+    Duration::new(time.tv_sec as u64, time.tv_usec as u32 * 1000)
+}
+
+impl CpuTime {
+    /// Returns None when the platform doesn't provide it.
+    #[cfg(unix)]
+    pub fn now() -> Option<Self> {
+        let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+
+        // SAFETY: getrusage fills usage when it succeeds.
+        if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+            return None;
+        }
+
+        let usage = unsafe { usage.assume_init() };
+
+        Some(CpuTime {
+            user: timeval_to_duration(usage.ru_utime),
+            system: timeval_to_duration(usage.ru_stime),
+        })
+    }
+
+    #[cfg(not(unix))]
+    pub fn now() -> Option<Self> {
+        None
+    }
+
+    pub fn total(&self) -> Duration {
+        self.user + self.system
+    }
+
+    /// Average number of busy cores during `wall`.
+    pub fn ratio(&self, wall: Duration) -> f64 {
+        if wall.is_zero() {
+            0.0
+        } else {
+            self.total().as_secs_f64() / wall.as_secs_f64()
+        }
+    }
+}
+
+impl Sub for CpuTime {
+    type Output = CpuTime;
+
+    fn sub(self, other: CpuTime) -> CpuTime {
+        CpuTime {
+            user: self.user.saturating_sub(other.user),
+            system: self.system.saturating_sub(other.system),
+        }
+    }
+}
+
+#[test]
+fn check_cpu_time() {
+    let start = CpuTime::now().unwrap();
+    let mut x = 0u64;
+
+    for i in 0..10_000_000u64 {
+        x = std::hint::black_box(x.wrapping_add(i * i));
+    }
+
+    let used = CpuTime::now().unwrap() - start;
+
+    assert!(used.user > Duration::ZERO, "{x}");
+    assert_eq!(
+        CpuTime {
+            user: Duration::from_millis(30),
+            system: Duration::from_millis(10),
+        }
+        .ratio(Duration::from_millis(20)),
+        2.0
+    );
+}
@@ -1 +1,9 @@
 automod::dir!("src/days/");
+
+// The panic hook is shared by the tests, it is left alone here.
+#[test]
+fn check_fuzz() {
+    for day_number in 1..=12 {
+        assert_eq!(advent_2025::fuzz_failures(day_number, 0..10, 20), []);
+    }
+}
@@ -1,3 +1,6 @@
+use advent_2025::parse::parse;
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
 use std::io::{BufRead, Lines};
 
 const DIAL_SIZE: i32 = 100;
@@ -6,41 +9,70 @@ fn resolve<T>(lines: Lines<T>) -> (i32, i32)
 where
     T: BufRead,
 {
-    let (_, p1, p2) = lines.fold((50, 0, 0), |(dial, acc1, acc2), line| {
+    let rotations: Vec<(bool, i32)> = lines
+        .map(|line| {
+            let line = line.unwrap();
+            let bytes = line.as_bytes();
+
+            (bytes[0] == b'L', parse(&bytes[1..]).unwrap())
+        })
+        .collect();
+
+    lap("parse");
+
+    let (mut dial, mut p1, mut p2) = (50, 0, 0);
+
+    for (left, clicks) in rotations {
+        let newdial = if left { dial - clicks } else { dial + clicks };
+
+        let (div, rem) = (newdial.div_euclid(DIAL_SIZE), newdial.rem_euclid(DIAL_SIZE));
+
+        p1 += (rem == 0) as i32; // check that we're on 0
+        p2 += div.abs()
+            + if left {
+                (rem == 0) as i32 // we're going back to 0, add a rotation not in div
+                    - (dial == 0) as i32 // we're starting from 0, but div wrongly count a rotation
+            } else {
+                0
+            };
+
+        dial = rem;
+    }
+
+    (p1, p2)
+}
+
+// Naive version turning the dial one click at a time.
+fn resolve_clicks<T>(lines: Lines<T>) -> (i32, i32)
+where
+    T: BufRead,
+{
+    let (mut dial, mut p1, mut p2) = (50, 0, 0);
+
+    for line in lines {
         let line = line.unwrap();
         let bytes = line.as_bytes();
-        let clicks = bytes
-            .iter()
-            .skip(1)
-            .fold(0, |acc, b| acc * 10 + (b - b'0') as i32);
+        let clicks: i32 = parse(&bytes[1..]).unwrap();
+        let step = if bytes[0] == b'L' { DIAL_SIZE - 1 } else { 1 };
 
-        let newdial = if bytes[0] == b'L' {
-            dial - clicks
-        } else {
-            dial + clicks
-        };
+        for _ in 0..clicks {
+            dial = (dial + step) % DIAL_SIZE;
 
-        let (div, rem) = (newdial.div_euclid(DIAL_SIZE), newdial.rem_euclid(DIAL_SIZE));
-        let (p1, p2) = (
-            (rem == 0) as i32, // check that we're on 0
-            div.abs()
-                + if bytes[0] == b'L' {
-                    (rem == 0) as i32 // we're going back to 0, add a rotation not in div
-                        - (dial == 0) as i32 // we're starting from 0, but div wrongly count a rotation
-                } else {
-                    0
-                },
-        );
-
-        (rem, acc1 + p1, acc2 + p2)
-    });
+            if dial == 0 {
+                p2 += 1;
+            }
+        }
+
+        if dial == 0 {
+            p1 += 1;
+        }
+    }
 
     (p1, p2)
 }
 
-#[test]
-fn check() {
-    const TEST: &str = "L68
+#[cfg(test)]
+const TEST: &str = "L68
 L30
 R48
 L5
@@ -50,9 +82,31 @@ L1
 L99
 R14
 L82";
+
+#[test]
+fn check() {
     use std::io::Cursor;
 
     assert_eq!(resolve(Cursor::new(TEST).lines()), (3, 6));
+    assert_eq!(resolve_clicks(Cursor::new(TEST).lines()), (3, 6));
+}
+
+// Scale is the number of rotations.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            let direction = if rng.chance(0.5) { 'L' } else { 'R' };
+
+            format!("{direction}{}\n", rng.range(1..=999))
+        })
+        .collect()
+}
+
+#[test]
+fn check_generate() {
+    let input = generate(&mut Rng::new(1), 100);
+
+    assert_eq!(input.lines().count(), 100);
 }
 
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
@@ -63,4 +117,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_clicks_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_clicks(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "clicks", resolve_clicks_string) }
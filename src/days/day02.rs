@@ -1,3 +1,6 @@
+use advent_2025::parse::parse;
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
 use std::io::{BufRead, Lines};
 
 fn find_invalid(min: u64, min_number_of_digits: usize, max: u64, split: usize) -> Vec<u64> {
@@ -41,8 +44,8 @@ fn find_invalid(min: u64, min_number_of_digits: usize, max: u64, split: usize) -
 #[cfg(test)]
 fn find_invalid_test(min: &[u8], max: &[u8], split: usize) -> Vec<u64> {
     let min_number_of_digits = min.len();
-    let min = min.iter().fold(0, |acc, c| acc * 10 + (c - b'0') as u64);
-    let max = max.iter().fold(0, |acc, c| acc * 10 + (c - b'0') as u64);
+    let min = parse(min).unwrap();
+    let max = parse(max).unwrap();
 
     find_invalid(min, min_number_of_digits, max, split)
 }
@@ -101,20 +104,24 @@ where
 {
     let line = lines.next().unwrap().unwrap();
     let (mut p1, mut p2) = (0, 0);
+    let ranges: Vec<(u64, usize, u64)> = line
+        .split(',')
+        .map(|range| {
+            let rs = range.split('-').collect::<Vec<_>>();
+            assert_eq!(rs.len(), 2);
+            let (min, max) = (rs[0].as_bytes(), rs[1].as_bytes());
 
-    for range in line.split(',') {
-        let rs = range.split('-').collect::<Vec<_>>();
-        assert_eq!(rs.len(), 2);
-        let (min, max) = (rs[0].as_bytes(), rs[1].as_bytes());
+            // 7 is the max prime number that can split the range values.
+            assert!(min.len() < 11);
+            assert!(max.len() < 11);
 
-        // 7 is the max prime number that can split the range values.
-        assert!(min.len() < 11);
-        assert!(max.len() < 11);
+            (parse(min).unwrap(), min.len(), parse(max).unwrap())
+        })
+        .collect();
 
-        let min_number_of_digits = min.len();
-        let min = min.iter().fold(0, |acc, c| acc * 10 + (c - b'0') as u64);
-        let max = max.iter().fold(0, |acc, c| acc * 10 + (c - b'0') as u64);
+    lap("parse");
 
+    for (min, min_number_of_digits, max) in ranges {
         let halves = find_invalid(min, min_number_of_digits, max, 2);
 
         p1 += halves.iter().sum::<u64>();
@@ -134,12 +141,73 @@ where
     (p1, p2)
 }
 
+// Naive version checking the digits of every ID of the ranges.
+fn is_repeated(digits: &[u8], split: usize) -> bool {
+    let len = digits.len();
+
+    len.is_multiple_of(split)
+        && digits
+            .chunks(len / split)
+            .all(|c| c == &digits[..len / split])
+}
+
+fn resolve_naive<T>(mut lines: Lines<T>) -> (u64, u64)
+where
+    T: BufRead,
+{
+    let line = lines.next().unwrap().unwrap();
+    let (mut p1, mut p2) = (0, 0);
+
+    for range in line.split(',') {
+        let (min, max) = range.split_once('-').unwrap();
+        let (min, max): (u64, u64) = (
+            parse(min.as_bytes()).unwrap(),
+            parse(max.as_bytes()).unwrap(),
+        );
+
+        for id in min..=max {
+            let digits = id.to_string();
+            let digits = digits.as_bytes();
+
+            if is_repeated(digits, 2) {
+                p1 += id;
+            }
+            if (2..=digits.len()).any(|split| is_repeated(digits, split)) {
+                p2 += id;
+            }
+        }
+    }
+
+    (p1, p2)
+}
+
+#[cfg(test)]
+const TEST: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
+
 #[test]
 fn check() {
-    const TEST: &str = "11-22,95-115,998-1012,1188511880-1188511890,222220-222224,1698522-1698528,446443-446449,38593856-38593862,565653-565659,824824821-824824827,2121212118-2121212124";
     use std::io::Cursor;
 
     assert_eq!(resolve(Cursor::new(TEST).lines()), (1227775554, 4174379265));
+    assert_eq!(
+        resolve_naive(Cursor::new(TEST).lines()),
+        (1227775554, 4174379265)
+    );
+}
+
+// Scale is the number of ranges.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    let ranges: Vec<String> = (0..scale)
+        .map(|_| {
+            let digits = rng.range(1..=10) as u32;
+            let min = rng.range(10u64.pow(digits - 1)..=10u64.pow(digits) - 1);
+            let max = (min + rng.range(0..=10_000)).min(9_999_999_999);
+
+            format!("{min}-{max}")
+        })
+        .collect();
+
+    ranges.join(",") + "\n"
 }
 
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
@@ -150,4 +218,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_naive_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_naive(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "naive", resolve_naive_string) }
@@ -1,3 +1,5 @@
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
 use std::io::{BufRead, Lines};
 
 // This function returns the first max.
@@ -40,14 +42,47 @@ fn find_joltage(bank: &[u8], batteries_number: usize) -> u64 {
 }
 
 fn resolve<T>(lines: Lines<T>) -> (u64, u64)
+where
+    T: BufRead,
+{
+    let banks: Vec<String> = lines.map(|line| line.unwrap()).collect();
+
+    lap("parse");
+
+    let p1 = banks
+        .iter()
+        .map(|bank| find_joltage(bank.as_bytes(), 2))
+        .sum();
+
+    lap("part1");
+
+    let p2 = banks
+        .iter()
+        .map(|bank| find_joltage(bank.as_bytes(), 12))
+        .sum();
+
+    lap("part2");
+
+    (p1, p2)
+}
+
+// Dynamic programming version, best[k] being the largest joltage
+// made of k batteries of the bank read so far.
+fn resolve_dp<T>(lines: Lines<T>) -> (u64, u64)
 where
     T: BufRead,
 {
     lines.fold((0, 0), |(p1, p2), line| {
         let line = line.unwrap();
-        let bank = line.as_bytes();
+        let mut best = [0u64; 13];
 
-        (p1 + find_joltage(bank, 2), p2 + find_joltage(bank, 12))
+        for (i, c) in line.bytes().enumerate() {
+            for k in (1..=12.min(i + 1)).rev() {
+                best[k] = best[k].max(best[k - 1] * 10 + (c - b'0') as u64);
+            }
+        }
+
+        (p1 + best[2], p2 + best[12])
     })
 }
 
@@ -60,6 +95,29 @@ fn check() {
     use std::io::Cursor;
 
     assert_eq!(resolve(Cursor::new(TEST).lines()), (357, 3121910778619));
+
+    assert_eq!(resolve_dp(Cursor::new(TEST).lines()), (357, 3121910778619));
+}
+
+// Scale is the number of banks of 100 batteries.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            let mut bank: String = (0..100)
+                .map(|_| (b'0' + rng.range(1..=9) as u8) as char)
+                .collect();
+
+            bank.push('\n');
+            bank
+        })
+        .collect()
+}
+
+#[test]
+fn check_generate() {
+    let input = generate(&mut Rng::new(1), 10);
+
+    assert_eq!(input.lines().count(), 10);
 }
 
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
@@ -70,4 +128,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_dp_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_dp(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "dp", resolve_dp_string) }
@@ -1,74 +1,42 @@
+use advent_2025::grid::{Connectivity, Grid};
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
+use advent_2025::trace;
 use std::io::{BufRead, Lines};
 
-const SIZE: usize = if cfg!(test) { 10 } else { 140 };
-const S: i32 = SIZE as i32;
-
-fn get_forklift(grid: &[[u8; SIZE]; SIZE]) -> Vec<(usize, usize)> {
-    let mut ret = vec![];
-
-    for y in 0..S {
-        for x in 0..S {
-            if grid[y as usize][x as usize] != b'@' {
-                continue;
-            }
-
-            let mut adjacents = 0;
-
-            for (dx, dy) in [
-                (-1, -1),
-                (0, -1),
-                (1, -1),
-                (-1, 0),
-                (1, 0),
-                (-1, 1),
-                (0, 1),
-                (1, 1),
-            ] {
-                if y + dy < 0 || y + dy >= S {
-                    continue;
-                }
-                if x + dx < 0 || x + dx >= S {
-                    continue;
-                }
-
-                if grid[(y + dy) as usize][(x + dx) as usize] == b'@' {
-                    adjacents += 1;
-                }
-            }
-
-            if adjacents < 4 {
-                ret.push((x as usize, y as usize));
-            }
-        }
-    }
-
-    ret
+fn get_forklift(grid: &Grid<u8>) -> Vec<(usize, usize)> {
+    grid.find_all(b'@')
+        .filter(|&(x, y)| {
+            grid.neighbors(x, y, Connectivity::Eight)
+                .filter(|&position| grid[position] == b'@')
+                .count()
+                < 4
+        })
+        .collect()
 }
 
 fn resolve<T>(lines: Lines<T>) -> (usize, usize)
 where
     T: BufRead,
 {
-    let mut grid = [[0; SIZE]; SIZE];
+    let mut grid = Grid::from_lines(lines).unwrap_or_else(|e| panic!("{e}"));
 
-    for (y, line) in lines.enumerate() {
-        let line = line.unwrap();
-
-        for (x, &c) in line.as_bytes().iter().enumerate() {
-            grid[y][x] = c;
-        }
-    }
+    lap("parse");
 
     let to_remove = get_forklift(&grid);
     let p1 = to_remove.len();
 
-    for (x, y) in to_remove.into_iter() {
-        grid[y][x] = b'.';
+    trace!(Info, "wave 1: {p1} rolls removed");
+
+    for position in to_remove.into_iter() {
+        grid[position] = b'.';
     }
 
+    lap("part1");
+
     let mut p2 = p1;
 
-    loop {
+    for wave in 2.. {
         let to_remove = get_forklift(&grid);
 
         if to_remove.is_empty() {
@@ -76,10 +44,49 @@ where
         }
 
         p2 += to_remove.len();
+        trace!(Info, "wave {wave}: {} rolls removed", to_remove.len());
+
+        for position in to_remove.into_iter() {
+            grid[position] = b'.';
+        }
+    }
 
-        for (x, y) in to_remove.into_iter() {
-            grid[y][x] = b'.';
+    lap("part2");
+
+    (p1, p2)
+}
+
+// Removes the rolls one by one, only checking again the neighbors of
+// the removed ones.
+fn resolve_queue<T>(lines: Lines<T>) -> (usize, usize)
+where
+    T: BufRead,
+{
+    let mut grid = Grid::from_lines(lines).unwrap_or_else(|e| panic!("{e}"));
+    let rolls = |grid: &Grid<u8>, (x, y)| {
+        grid.neighbors(x, y, Connectivity::Eight)
+            .filter(|&position| grid[position] == b'@')
+            .count()
+    };
+    let mut queue: Vec<(usize, usize)> = grid
+        .find_all(b'@')
+        .filter(|&position| rolls(&grid, position) < 4)
+        .collect();
+    let p1 = queue.len();
+    let mut p2 = 0;
+
+    while let Some(position) = queue.pop() {
+        if grid[position] != b'@' || rolls(&grid, position) >= 4 {
+            continue;
         }
+
+        grid[position] = b'.';
+        p2 += 1;
+
+        queue.extend(
+            grid.neighbors(position.0, position.1, Connectivity::Eight)
+                .filter(|&neighbor| grid[neighbor] == b'@'),
+        );
     }
 
     (p1, p2)
@@ -100,6 +107,43 @@ fn check() {
     use std::io::Cursor;
 
     assert_eq!(resolve(Cursor::new(TEST).lines()), (13, 43));
+
+    assert_eq!(resolve_queue(Cursor::new(TEST).lines()), (13, 43));
+}
+
+#[test]
+#[should_panic(expected = "line 3 has a different width than the first one")]
+fn check_mismatched_line() {
+    const TEST: &str = "@@@
+@.@
+@@";
+    use std::io::Cursor;
+
+    resolve(Cursor::new(TEST).lines());
+}
+
+// Scale is the side of the square grid.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut grid = String::with_capacity(scale * (scale + 1));
+
+    for _ in 0..scale {
+        for _ in 0..scale {
+            grid.push(if rng.chance(0.6) { '@' } else { '.' });
+        }
+        grid.push('\n');
+    }
+
+    grid
+}
+
+#[test]
+fn check_generate() {
+    use std::io::Cursor;
+
+    let input = generate(&mut Rng::new(1), 30);
+    let (p1, p2) = resolve(Cursor::new(&input).lines());
+
+    assert!(p1 <= p2);
 }
 
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
@@ -110,4 +154,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_queue_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_queue(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "queue", resolve_queue_string) }
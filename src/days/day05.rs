@@ -1,138 +1,94 @@
+use advent_2025::parse::parse;
+use advent_2025::random::Rng;
+use advent_2025::rangeset::RangeSet;
+use advent_2025::timing::lap;
+use advent_2025::Paragrapher;
+use std::error::Error;
 use std::io::{BufRead, Lines};
+use std::ops::RangeInclusive;
 
-// This function tries to merge range with the last element of the
-// vector. The idea here is that the vector is sorted, and range will
-// be added at the end.
-fn push_merge(v: &mut Vec<(u64, u64)>, range: (u64, u64)) {
-    if let Some(last) = v.last_mut() {
-        if last.1 + 1 >= range.0 {
-            last.1 = last.1.max(range.1);
-            return;
-        }
-    }
-    v.push(range);
-}
-
-#[test]
-fn check_push_merge() {
-    let mut v = vec![];
-
-    push_merge(&mut v, (0, 2));
-
-    assert_eq!(v, [(0, 2)]);
-
-    push_merge(&mut v, (4, 5));
-
-    assert_eq!(v, [(0, 2), (4, 5)]);
-
-    push_merge(&mut v, (5, 6));
+fn parse_range(line: &str) -> Result<RangeInclusive<u64>, Box<dyn Error>> {
+    let (first, last) = line.split_once('-').ok_or("missing '-' in range")?;
 
-    assert_eq!(v, [(0, 2), (4, 6)]);
-
-    push_merge(&mut v, (7, 8));
-
-    assert_eq!(v, [(0, 2), (4, 8)]);
-
-    push_merge(&mut v, (5, 6));
-
-    assert_eq!(v, [(0, 2), (4, 8)]);
+    Ok(parse(first.as_bytes())?..=parse(last.as_bytes())?)
 }
 
-// This function generates a vector of range by adding and merging a
-// new range. The vector is sorted so we first try to find where the
-// range must be inserted. When found, let's try to merge with the
-// previous element and then try to merge the remaining ranges.
-fn add_and_merge(v: Vec<(u64, u64)>, range: (u64, u64)) -> Vec<(u64, u64)> {
-    let mut ret = vec![];
-    let index = v.iter().position(|(first, _)| first > &range.0);
-
-    if let Some(i) = index {
-        ret.extend(&v[..i]);
-        push_merge(&mut ret, range);
-
-        for &r in v[i..].iter() {
-            push_merge(&mut ret, r);
-        }
-    } else {
-        ret.extend(v);
-        push_merge(&mut ret, range);
-    }
-
-    ret
-}
-
-#[test]
-fn check_add_and_merge() {
-    let mut v = vec![(0, 2)];
-
-    v = add_and_merge(v, (3, 4));
-
-    assert_eq!(v, vec![(0, 4)]);
-
-    v = add_and_merge(v, (10, 12));
-
-    assert_eq!(v, vec![(0, 4), (10, 12)]);
-
-    v = add_and_merge(v, (10, 12));
-
-    assert_eq!(v, vec![(0, 4), (10, 12)]);
-
-    v = add_and_merge(v, (6, 8));
-
-    assert_eq!(v, vec![(0, 4), (6, 8), (10, 12)]);
-
-    v = add_and_merge(v, (5, 7));
-
-    assert_eq!(v, vec![(0, 8), (10, 12)]);
+fn resolve<T>(lines: Lines<T>) -> (i32, u64)
+where
+    T: BufRead,
+{
+    let (ranges, ingredients) = lines
+        .two_sections(
+            |p| p[0].contains('-'),
+            |p| {
+                p.iter()
+                    .map(|line| parse_range(line))
+                    .collect::<Result<Vec<_>, _>>()
+            },
+            |line| Ok::<u64, Box<dyn Error>>(parse(line.as_bytes())?),
+        )
+        .unwrap_or_else(|e| panic!("{e}"));
+
+    let ranges: RangeSet = ranges.into_iter().flatten().collect();
+
+    lap("parse");
+
+    let p1 = ingredients
+        .into_iter()
+        .filter(|&ingredient| ranges.contains(ingredient))
+        .count() as i32;
 
-    v = add_and_merge(v, (2, 9));
+    lap("part1");
 
-    assert_eq!(v, vec![(0, 12)]);
+    let p2 = u64::try_from(ranges.len()).expect("too many fresh ingredients");
 
-    v = add_and_merge(v, (15, 20));
-    v = add_and_merge(v, (10, 14));
+    lap("part2");
 
-    assert_eq!(v, vec![(0, 20)]);
+    (p1, p2)
 }
 
-fn resolve<T>(lines: Lines<T>) -> (i32, u64)
+// Naive version checking every range and merging the sorted ranges.
+fn resolve_naive<T>(lines: Lines<T>) -> (i32, u64)
 where
     T: BufRead,
 {
-    let mut is_range: bool = true;
-    let mut ranges = vec![];
-    let mut p1 = 0;
+    let mut lines = lines.map(|line| line.unwrap());
+    let mut ranges: Vec<(u64, u64)> = lines
+        .by_ref()
+        .take_while(|line| !line.is_empty())
+        .map(|line| {
+            let (first, last) = line.split_once('-').unwrap();
+
+            (first.parse().unwrap(), last.parse().unwrap())
+        })
+        .collect();
+
+    let p1 = lines
+        .map(|line| line.parse::<u64>().unwrap())
+        .filter(|&ingredient| {
+            ranges
+                .iter()
+                .any(|&(first, last)| first <= ingredient && ingredient <= last)
+        })
+        .count() as i32;
 
-    for line in lines {
-        let line = line.unwrap();
+    ranges.sort_unstable();
 
-        if line.is_empty() {
-            is_range = false;
-            continue;
-        }
+    let (mut p2, mut next) = (0, Some(0));
 
-        if is_range {
-            let rs: Vec<u64> = line.split('-').map(|x| x.parse::<u64>().unwrap()).collect();
-            assert_eq!(rs.len(), 2);
-            let (first, last) = (rs[0], rs[1]);
+    for (first, last) in ranges {
+        // Nothing is left after a range ending at u64::MAX.
+        let Some(start) = next else {
+            break;
+        };
+        let first = first.max(start);
 
-            ranges = add_and_merge(ranges, (first, last));
-        } else {
-            let ingredient = line.parse::<u64>().unwrap();
-
-            if !ranges
-                .iter()
-                .all(|&(first, last)| ingredient < first || ingredient > last)
-            {
-                p1 += 1;
-            }
+        if first <= last {
+            p2 += last - first + 1;
+            next = last.checked_add(1);
         }
     }
 
-    let p2 = ranges
-        .into_iter()
-        .fold(0, |total, (first, last)| total + last - first + 1);
-
     (p1, p2)
 }
 
@@ -152,6 +108,62 @@ fn check() {
     use std::io::Cursor;
 
     assert_eq!(resolve(Cursor::new(TEST).lines()), (3, 14));
+
+    assert_eq!(resolve_naive(Cursor::new(TEST).lines()), (3, 14));
+}
+
+#[test]
+fn check_naive_max() {
+    const TEST: &str = "5-18446744073709551615
+10-18446744073709551615
+
+7";
+    use std::io::Cursor;
+
+    assert_eq!(resolve_naive(Cursor::new(TEST).lines()), (1, u64::MAX - 4));
+}
+
+// Scale is the number of ranges, and the number of ingredients.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    let ranges: Vec<(u64, u64)> = (0..scale)
+        .map(|_| {
+            let first = rng.range(1..=100_000_000_000_000);
+
+            (first, first + rng.range(0..=10_000_000_000_000))
+        })
+        .collect();
+    let mut input = String::new();
+
+    for (first, last) in ranges.iter() {
+        input += &format!("{first}-{last}\n");
+    }
+
+    input.push('\n');
+
+    for _ in 0..scale {
+        // Half of the ingredients are fresh.
+        let ingredient = if rng.chance(0.5) {
+            let &(first, last) = rng.choose(&ranges).expect("as many ranges as ingredients");
+
+            rng.range(first..=last)
+        } else {
+            rng.range(1..=110_000_000_000_000)
+        };
+
+        input += &format!("{ingredient}\n");
+    }
+
+    input
+}
+
+#[test]
+fn check_generate() {
+    use std::io::Cursor;
+
+    let input = generate(&mut Rng::new(1), 50);
+    let (p1, _) = resolve(Cursor::new(&input).lines());
+
+    assert!(p1 >= 25);
 }
 
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
@@ -162,4 +174,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_naive_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_naive(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "naive", resolve_naive_string) }
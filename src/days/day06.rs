@@ -1,48 +1,43 @@
+use advent_2025::grid::Grid;
+use advent_2025::parse::{parse, parse_digits};
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
 use std::io::{BufRead, Lines};
 
-const WIDTH: usize = if cfg!(test) { 15 } else { 3745 };
-const LINES: usize = if cfg!(test) { 4 } else { 5 };
-
 fn resolve<T>(lines: Lines<T>) -> (u64, u64)
 where
     T: BufRead,
 {
-    let mut worksheet: [Vec<u8>; LINES] = Default::default();
+    let worksheet = Grid::from_lines(lines).unwrap_or_else(|e| panic!("{e}"));
     let (mut p1, mut p2) = (0, 0);
 
-    for (y, line) in lines.enumerate() {
-        let line = line.unwrap();
+    assert!(worksheet.height() > 0, "empty worksheet");
 
-        for &c in line.as_bytes().iter() {
-            worksheet[y].push(c);
-        }
-    }
+    lap("parse");
 
-    let operations = &worksheet[LINES - 1];
+    let numbers_len = worksheet.height() - 1;
+    let operations = worksheet.row(numbers_len);
 
-    let mut end = WIDTH;
-
-    assert!(operations.len() <= WIDTH);
+    let mut end = operations.len();
 
     while let Some(index) = operations[..end].iter().rposition(|&c| c != b' ') {
-        let p1_numbers = worksheet[0..LINES - 1].iter().map(|line| {
-            line[index..end]
-                .iter()
-                .filter(|&&c| c != b' ')
-                .fold(0, |acc, &c| acc * 10 + (c - b'0') as u64)
-        });
+        let p1_numbers = worksheet
+            .rows()
+            .take(numbers_len)
+            .map(|line| parse::<u64>(line[index..end].trim_ascii()).unwrap());
 
         let p2_numbers = (index..end).filter_map(|x| {
-            let number = worksheet[0..LINES - 1]
-                .iter()
-                .filter(|line| line[x] != b' ')
-                .fold(0, |acc, line| acc * 10 + (line[x] - b'0') as u64);
+            let mut digits = worksheet
+                .column(x)
+                .take(numbers_len)
+                .copied()
+                .filter(|&c| c != b' ')
+                .peekable();
 
-            if number == 0 {
-                None
-            } else {
-                Some(number)
-            }
+            // Blank columns are separating problems.
+            digits.peek()?;
+
+            Some(parse_digits::<u64, _>(digits).unwrap())
         });
 
         match operations[index] {
@@ -74,6 +69,182 @@ fn check() {
     assert_eq!(resolve(Cursor::new(TEST).lines()), (4277556, 3263827));
 }
 
+#[test]
+#[should_panic(expected = "line 2 has a different width than the first one")]
+fn check_mismatched_line() {
+    const TEST: &str = "12 3
+4 5
++  *";
+    use std::io::Cursor;
+
+    resolve(Cursor::new(TEST).lines());
+}
+
+// Scale is the number of problems, each one has 4 numbers of up to
+// 3 digits aligned either on the left or on the right.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut rows = vec![String::new(); 5];
+
+    for problem in 0..scale {
+        let width = rng.range_usize(1..=3);
+        let widest = rng.range_usize(0..=3);
+        let left = rng.chance(0.5);
+
+        if problem > 0 {
+            rows.iter_mut().for_each(|row| row.push(' '));
+        }
+
+        for (i, row) in rows.iter_mut().take(4).enumerate() {
+            let digits = if i == widest {
+                width
+            } else {
+                rng.range_usize(1..=width)
+            };
+            let number: String = (0..digits)
+                .map(|_| (b'0' + rng.range(1..=9) as u8) as char)
+                .collect();
+
+            if left {
+                *row += &format!("{number:<width$}");
+            } else {
+                *row += &format!("{number:>width$}");
+            }
+        }
+
+        let operation = if rng.chance(0.5) { '+' } else { '*' };
+
+        rows[4] += &format!("{operation:<width$}");
+    }
+
+    rows.into_iter().map(|row| row + "\n").collect()
+}
+
+#[test]
+fn check_generate() {
+    let input = generate(&mut Rng::new(1), 30);
+
+    assert_eq!(input.lines().count(), 5);
+}
+
+// For the shrinking, the problems are the sections and their columns
+// the items, keeping the rows aligned.
+fn split_problems(input: &str) -> Vec<Vec<String>> {
+    let rows: Vec<Vec<char>> = input.lines().map(|row| row.chars().collect()).collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let mut problems = vec![];
+    let mut problem = vec![];
+
+    for x in 0..width {
+        let column: String = rows
+            .iter()
+            .map(|row| row.get(x).copied().unwrap_or(' '))
+            .collect();
+
+        if column.trim().is_empty() {
+            if !problem.is_empty() {
+                problems.push(std::mem::take(&mut problem));
+            }
+        } else {
+            problem.push(column);
+        }
+    }
+    if !problem.is_empty() {
+        problems.push(problem);
+    }
+
+    problems
+}
+
+fn join_problems(problems: &[Vec<String>]) -> String {
+    let columns: Vec<Vec<char>> = problems
+        .iter()
+        .flat_map(|problem| {
+            let height = problem[0].chars().count();
+
+            std::iter::once(vec![' '; height]).chain(problem.iter().map(|c| c.chars().collect()))
+        })
+        .skip(1)
+        .collect();
+    let height = columns.first().map_or(0, Vec::len);
+
+    (0..height)
+        .map(|y| columns.iter().map(|column| column[y]).collect::<String>() + "\n")
+        .collect()
+}
+
+#[test]
+fn check_problems() {
+    let input = generate(&mut Rng::new(1), 10);
+    let problems = split_problems(&input);
+
+    assert_eq!(problems.len(), 10);
+    assert_eq!(join_problems(&problems), input);
+    assert_eq!(join_problems(&problems[3..5]).lines().count(), 5);
+}
+
+// Reference splitting the rows on the whitespaces for the first part,
+// and the problems in columns for the second one.
+fn resolve_split<T>(lines: Lines<T>) -> (u64, u64)
+where
+    T: BufRead,
+{
+    let input: String = lines.map(|line| line.unwrap() + "\n").collect();
+    let mut rows: Vec<Vec<&str>> = input
+        .lines()
+        .map(|row| row.split_whitespace().collect())
+        .collect();
+    let operations = rows.pop().expect("empty worksheet");
+    let evaluate = |operation: &str, numbers: &mut dyn Iterator<Item = u64>| match operation {
+        "+" => numbers.sum::<u64>(),
+        "*" => numbers.product::<u64>(),
+        _ => panic!("unknown operations {operation}"),
+    };
+
+    let p1 = operations
+        .iter()
+        .enumerate()
+        .map(|(i, operation)| {
+            evaluate(
+                operation,
+                &mut rows.iter().map(|row| row[i].parse().unwrap()),
+            )
+        })
+        .sum();
+
+    let p2 = split_problems(&input)
+        .iter()
+        .map(|columns| {
+            // The operation is at the bottom of the first column.
+            let operation = &columns[0][columns[0].len() - 1..];
+
+            evaluate(
+                operation,
+                &mut columns.iter().map(|column| {
+                    let digits: String = column[..column.len() - 1]
+                        .chars()
+                        .filter(|&c| c != ' ')
+                        .collect();
+
+                    digits.parse().unwrap()
+                }),
+            )
+        })
+        .sum();
+
+    (p1, p2)
+}
+
+#[test]
+fn check_split() {
+    const TEST: &str = "123 328  51 64 
+ 45 64  387 23 
+  6 98  215 314
+*   +   *   +  ";
+    use std::io::Cursor;
+
+    assert_eq!(resolve_split(Cursor::new(TEST).lines()), (4277556, 3263827));
+}
+
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
 where
     T: BufRead,
@@ -82,4 +253,15 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_split_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_split(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Sections::new(file!(), split_problems, join_problems) }
+inventory::submit! { advent_2025::Variant::new(file!(), "split", resolve_split_string) }
@@ -1,24 +1,27 @@
+use advent_2025::grid::Grid;
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
+use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, Lines};
 
-const SIZE: usize = if cfg!(test) { 15 } else { 141 };
-
 fn resolve<T>(lines: Lines<T>) -> (usize, usize)
 where
     T: BufRead,
 {
+    let manifold = Grid::from_lines(lines).unwrap_or_else(|e| panic!("{e}"));
+    let size = manifold.width();
     let mut p1 = 0;
-    let mut beams = vec![0usize; SIZE];
+    let mut beams = vec![0usize; size];
 
-    beams[SIZE / 2] = 1;
+    // Start position
+    let (start, _) = manifold.find(b'S').expect("no start position");
 
-    for line in lines {
-        let line = line.unwrap();
-        let line = line.as_bytes();
+    beams[start] = 1;
 
-        assert!(line.len() == SIZE);
+    lap("parse");
 
-        // Start position
-        let mut tmp = vec![0usize; SIZE];
+    for line in manifold.rows() {
+        let mut tmp = vec![0usize; size];
 
         for (index, paths) in beams
             .into_iter()
@@ -29,7 +32,7 @@ where
                 if index > 0 && line[index - 1] != b'^' {
                     tmp[index - 1] += paths;
                 }
-                if index < SIZE - 1 && line[index + 1] != b'^' {
+                if index < size - 1 && line[index + 1] != b'^' {
                     tmp[index + 1] += paths;
                 }
                 p1 += 1;
@@ -44,6 +47,50 @@ where
     (p1, beams.into_iter().sum())
 }
 
+// Recursive version following every beam down, with a cache of the
+// number of timelines from a position.
+fn resolve_recursive<T>(lines: Lines<T>) -> (usize, usize)
+where
+    T: BufRead,
+{
+    fn timelines(
+        manifold: &Grid<u8>,
+        (x, y): (usize, usize),
+        splitters: &mut HashSet<(usize, usize)>,
+        cache: &mut HashMap<(usize, usize), usize>,
+    ) -> usize {
+        if y == manifold.height() {
+            return 1;
+        }
+        if let Some(&count) = cache.get(&(x, y)) {
+            return count;
+        }
+
+        let count = if manifold[(x, y)] == b'^' {
+            splitters.insert((x, y));
+
+            [x.checked_sub(1), Some(x + 1)]
+                .into_iter()
+                .flatten()
+                .filter(|&x| manifold.get(x, y).is_some_and(|&c| c != b'^'))
+                .map(|x| timelines(manifold, (x, y + 1), splitters, cache))
+                .sum()
+        } else {
+            timelines(manifold, (x, y + 1), splitters, cache)
+        };
+
+        cache.insert((x, y), count);
+        count
+    }
+
+    let manifold = Grid::from_lines(lines).unwrap_or_else(|e| panic!("{e}"));
+    let start = manifold.find(b'S').expect("no start position");
+    let mut splitters = HashSet::new();
+    let p2 = timelines(&manifold, start, &mut splitters, &mut HashMap::new());
+
+    (splitters.len(), p2)
+}
+
 #[test]
 fn check() {
     const TEST: &str = ".......S.......
@@ -65,6 +112,45 @@ fn check() {
     use std::io::Cursor;
 
     assert_eq!(resolve(Cursor::new(TEST).lines()), (21, 40));
+
+    assert_eq!(resolve_recursive(Cursor::new(TEST).lines()), (21, 40));
+}
+
+#[test]
+#[should_panic(expected = "line 3 has a different width than the first one")]
+fn check_mismatched_line() {
+    const TEST: &str = "..S..
+.....
+..^.";
+    use std::io::Cursor;
+
+    resolve(Cursor::new(TEST).lines());
+}
+
+// Scale is the width of the manifold, the number of splitter rows is
+// limited so that the number of timelines fits in 64 bits.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    let width = scale.max(3) | 1;
+    let empty = ".".repeat(width) + "\n";
+    let mut manifold = format!("{:.^width$}\n", 'S') + &empty;
+
+    for _ in 0..(scale / 2).clamp(1, 40) {
+        let row: String = (0..width)
+            .map(|_| if rng.chance(0.3) { '^' } else { '.' })
+            .collect();
+
+        manifold += &(row + "\n");
+        manifold += &empty;
+    }
+
+    manifold
+}
+
+#[test]
+fn check_generate() {
+    let input = generate(&mut Rng::new(1), 41);
+
+    assert_eq!(input.lines().next().unwrap().find('S'), Some(20));
 }
 
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
@@ -75,4 +161,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_recursive_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_recursive(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "recursive", resolve_recursive_string) }
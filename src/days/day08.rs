@@ -1,74 +1,21 @@
+use advent_2025::geom::Point3;
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
+use advent_2025::trace;
 use std::io::{BufRead, Lines};
-use std::str::FromStr;
 
 const PAIRS: usize = if cfg!(test) { 10 } else { 1000 };
 
-#[derive(Debug, PartialEq)]
-struct Junction {
-    x: i64,
-    y: i64,
-    z: i64,
-}
-
-impl Junction {
-    fn square_distance(&self, other: &Junction) -> i64 {
-        (other.x - self.x) * (other.x - self.x)
-            + (other.y - self.y) * (other.y - self.y)
-            + (other.z - self.z) * (other.z - self.z)
-    }
-}
-
-#[derive(Debug, PartialEq)]
-struct ParseJunctionError;
-
-impl FromStr for Junction {
-    type Err = ParseJunctionError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let positions: Vec<&str> = s.split(',').collect();
-
-        if positions.len() != 3 {
-            return Err(ParseJunctionError);
-        }
-
-        let x = positions[0]
-            .trim()
-            .parse()
-            .map_err(|_| ParseJunctionError)?;
-        let y = positions[1]
-            .trim()
-            .parse()
-            .map_err(|_| ParseJunctionError)?;
-        let z = positions[2]
-            .trim()
-            .parse()
-            .map_err(|_| ParseJunctionError)?;
-
-        Ok(Junction { x, y, z })
-    }
-}
-
-#[test]
-fn check_junction() {
-    assert_eq!(
-        Junction::from_str("1,2,3"),
-        Ok(Junction { x: 1, y: 2, z: 3 })
-    );
-    assert_eq!(Junction::from_str("1,2"), Err(ParseJunctionError));
-    assert_eq!(
-        Junction::from_str("1, 2, 3"),
-        Ok(Junction { x: 1, y: 2, z: 3 })
-    );
-}
+type Junction = Point3<i64>;
 
 fn resolve<T>(lines: Lines<T>) -> (usize, i64)
 where
     T: BufRead,
 {
     let (mut p1, mut p2) = (vec![], 0);
-    let junctions: Vec<Junction> = lines
-        .map(|line| Junction::from_str(&line.unwrap()).unwrap())
-        .collect();
+    let junctions: Vec<Junction> = lines.map(|line| line.unwrap().parse().unwrap()).collect();
+
+    lap("parse");
 
     let size = junctions.len();
     let mut connections = vec![];
@@ -97,7 +44,19 @@ where
             let second_index = second_index + first_index + 1;
             let to_merge = circuits.swap_remove(second_index);
 
+            trace!(
+                Debug,
+                "connection {n}: merging circuits of {} and {} junctions, {} left",
+                circuits[first_index].len(),
+                to_merge.len(),
+                circuits.len()
+            );
+
             if circuits.len() == 1 {
+                // Everything is connected before the end of part 1.
+                if n < PAIRS {
+                    p1 = vec![size];
+                }
                 p2 = junctions[i].x * junctions[j].x;
                 break;
             }
@@ -107,17 +66,95 @@ where
 
         if n == PAIRS - 1 {
             p1 = circuits.iter().map(|c| c.len()).collect::<Vec<_>>();
+            lap("part1");
         }
     }
 
+    lap("part2");
+
     p1.sort_unstable();
 
     (p1.into_iter().rev().take(3).product(), p2)
 }
 
-#[test]
-fn check() {
-    const TEST: &str = "162,817,812
+fn find(parents: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+
+    while parents[root] != root {
+        root = parents[root];
+    }
+
+    // Path compression
+    let mut i = i;
+
+    while parents[i] != root {
+        let next = parents[i];
+
+        parents[i] = root;
+        i = next;
+    }
+
+    root
+}
+
+// Same algorithm using a union-find instead of merging circuit lists.
+fn resolve_union_find<T>(lines: Lines<T>) -> (usize, i64)
+where
+    T: BufRead,
+{
+    let (mut p1, mut p2) = (0, 0);
+    let junctions: Vec<Junction> = lines.map(|line| line.unwrap().parse().unwrap()).collect();
+
+    let size = junctions.len();
+    let mut connections = vec![];
+
+    for i in 0..size - 1 {
+        for j in i + 1..size {
+            connections.push((junctions[i].square_distance(&junctions[j]), i, j));
+        }
+    }
+
+    connections.sort_unstable_by_key(|&(d, _, _)| d);
+
+    let mut parents: Vec<usize> = (0..size).collect();
+    let mut sizes = vec![1; size];
+    let mut circuits = size;
+
+    for (n, &(_, i, j)) in connections.iter().enumerate() {
+        let (a, b) = (find(&mut parents, i), find(&mut parents, j));
+
+        if a != b {
+            let (big, small) = if sizes[a] < sizes[b] { (b, a) } else { (a, b) };
+
+            parents[small] = big;
+            sizes[big] += sizes[small];
+            circuits -= 1;
+
+            if circuits == 1 {
+                if n < PAIRS {
+                    p1 = size;
+                }
+                p2 = junctions[i].x * junctions[j].x;
+                break;
+            }
+        }
+
+        if n == PAIRS - 1 {
+            let mut roots: Vec<usize> = (0..size)
+                .filter(|&k| parents[k] == k)
+                .map(|k| sizes[k])
+                .collect();
+
+            roots.sort_unstable();
+            p1 = roots.into_iter().rev().take(3).product();
+        }
+    }
+
+    (p1, p2)
+}
+
+#[cfg(test)]
+const TEST: &str = "162,817,812
 57,618,57
 906,360,560
 592,479,940
@@ -137,9 +174,35 @@ fn check() {
 862,61,35
 984,92,344
 425,690,689";
+
+#[test]
+fn check() {
     use std::io::Cursor;
 
     assert_eq!(resolve(Cursor::new(TEST).lines()), (40, 25272));
+    assert_eq!(resolve_union_find(Cursor::new(TEST).lines()), (40, 25272));
+}
+
+// Scale is the number of junction boxes.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    (0..scale)
+        .map(|_| {
+            let [x, y, z] = [(); 3].map(|_| rng.range(0..=99_999));
+
+            format!("{x},{y},{z}\n")
+        })
+        .collect()
+}
+
+#[test]
+fn check_generate() {
+    use std::io::Cursor;
+
+    // Only 6 pairs, everything is connected before PAIRS.
+    let input = generate(&mut Rng::new(1), 4);
+
+    assert_eq!(resolve(Cursor::new(&input).lines()).0, 4);
+    assert_eq!(resolve_union_find(Cursor::new(&input).lines()).0, 4);
 }
 
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
@@ -150,4 +213,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_union_find_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_union_find(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "union-find", resolve_union_find_string) }
@@ -1,51 +1,23 @@
+use advent_2025::geom::{Point2, Rect};
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
 use std::io::{BufRead, Lines};
-use std::str::FromStr;
 
-#[derive(Debug, PartialEq)]
-struct Tile {
-    x: i64,
-    y: i64,
-}
-
-impl Tile {
-    fn surface(&self, other: &Tile) -> i64 {
-        ((other.x - self.x).abs() + 1) * ((other.y - self.y).abs() + 1)
-    }
+type Tile = Point2<i64>;
 
-    fn vector(&self, other: &Tile) -> (i64, i64) {
-        ((other.x - self.x), (other.y - self.y))
-    }
+fn surface(a: &Tile, b: &Tile) -> i64 {
+    Rect::from_corners(a, b).cell_count()
 }
 
 #[test]
 fn check_surface() {
-    let a = Tile { x: 1, y: 1 };
-
-    assert_eq!(2, a.surface(&Tile { x: 2, y: 1 }));
-    assert_eq!(3, a.surface(&Tile { x: 1, y: 3 }));
-
-    assert_eq!(4, a.surface(&Tile { x: 2, y: 2 }));
-    assert_eq!(12, a.surface(&Tile { x: 4, y: 3 }));
-}
+    let a = Tile::new(1, 1);
 
-#[derive(Debug, PartialEq)]
-struct ParseTileError;
+    assert_eq!(2, surface(&a, &Tile::new(2, 1)));
+    assert_eq!(3, surface(&a, &Tile::new(1, 3)));
 
-impl FromStr for Tile {
-    type Err = ParseTileError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let positions: Vec<&str> = s.split(',').collect();
-
-        if positions.len() != 2 {
-            return Err(ParseTileError);
-        }
-
-        let x = positions[0].trim().parse().map_err(|_| ParseTileError)?;
-        let y = positions[1].trim().parse().map_err(|_| ParseTileError)?;
-
-        Ok(Tile { x, y })
-    }
+    assert_eq!(4, surface(&a, &Tile::new(2, 2)));
+    assert_eq!(12, surface(&a, &Tile::new(4, 3)));
 }
 
 fn is_segments_intersect(
@@ -103,30 +75,31 @@ where
     T: BufRead,
 {
     let (mut p1, mut p2) = (0, 0);
-    let tiles: Vec<Tile> = lines
-        .map(|line| Tile::from_str(&line.unwrap()).unwrap())
-        .collect();
+    let tiles: Vec<Tile> = lines.map(|line| line.unwrap().parse().unwrap()).collect();
     let tiles_len = tiles.len();
 
+    lap("parse");
+
+    for i in 0..tiles_len - 1 {
+        for j in i + 1..tiles_len {
+            p1 = p1.max(surface(&tiles[i], &tiles[j]));
+        }
+    }
+
+    lap("part1");
+
     // finding external angle points
     let mut external_angle_tiles = vec![];
-    let mut prev = tiles[tiles.len() - 1].vector(&tiles[0]);
+    let mut prev = tiles[0] - tiles[tiles.len() - 1];
 
     for i in 0..tiles_len {
-        let (a, b) = (&tiles[i], &tiles[(i + 1) % tiles_len]);
-        let v = a.vector(b);
-        let product = prev.0 * v.1 - prev.1 * v.0;
-
-        let external_tile = if product > 0 {
-            Tile {
-                x: tiles[i].x + prev.0.signum() - v.0.signum(),
-                y: tiles[i].y + prev.1.signum() - v.1.signum(),
-            }
+        let (a, b) = (tiles[i], tiles[(i + 1) % tiles_len]);
+        let v = b - a;
+
+        let external_tile = if prev.cross(&v) > 0 {
+            a + prev.signum() - v.signum()
         } else {
-            Tile {
-                x: tiles[i].x - prev.0.signum() + v.0.signum(),
-                y: tiles[i].y - prev.1.signum() + v.1.signum(),
-            }
+            a - prev.signum() + v.signum()
         };
 
         external_angle_tiles.push(external_tile);
@@ -164,9 +137,7 @@ where
     for i in 0..tiles_len - 1 {
         for j in i + 1..tiles_len {
             let (a, b) = (&tiles[i], &tiles[j]);
-            let surface = a.surface(b);
-
-            p1 = p1.max(surface);
+            let surface = surface(a, b);
 
             if surface > p2 {
                 let xs = if a.x < b.x { (a.x, b.x) } else { (b.x, a.x) };
@@ -185,6 +156,8 @@ where
         }
     }
 
+    lap("part2");
+
     (p1, p2)
 }
 
@@ -203,6 +176,159 @@ fn check() {
     assert_eq!(resolve(Cursor::new(TEST).lines()), (50, 24));
 }
 
+// Reference checking every cell of the rectangles against the loop,
+// on a grid compressed to the coordinates of the red tiles and the gaps
+// between them.
+fn resolve_grid<T>(lines: Lines<T>) -> (i64, i64)
+where
+    T: BufRead,
+{
+    let tiles: Vec<Tile> = lines.map(|line| line.unwrap().parse().unwrap()).collect();
+    let compress = |values: Vec<i64>| {
+        let mut values = values;
+
+        values.sort_unstable();
+        values.dedup();
+
+        // Doubled, the gaps are at odd positions between the tiles.
+        let mut doubled = vec![values[0] * 2];
+
+        for pair in values.windows(2) {
+            doubled.extend([pair[0] + pair[1], pair[1] * 2]);
+        }
+
+        doubled
+    };
+    let xs = compress(tiles.iter().map(|tile| tile.x).collect());
+    let ys = compress(tiles.iter().map(|tile| tile.y).collect());
+    let edges: Vec<(Tile, Tile)> = (0..tiles.len())
+        .map(|i| (tiles[i] * 2, tiles[(i + 1) % tiles.len()] * 2))
+        .collect();
+
+    let inside = |x: i64, y: i64| {
+        let mut crossings = 0;
+
+        for (a, b) in &edges {
+            let (x_range, y_range) = (a.x.min(b.x)..=a.x.max(b.x), a.y.min(b.y)..=a.y.max(b.y));
+
+            if x_range.contains(&x) && y_range.contains(&y) {
+                return true;
+            }
+            if a.x == b.x && a.x > x && (a.y.min(b.y)..a.y.max(b.y)).contains(&y) {
+                crossings += 1;
+            }
+        }
+
+        crossings % 2 == 1
+    };
+
+    // Counts of the cells outside the loop, before each position.
+    let mut outside = vec![vec![0; ys.len() + 1]; xs.len() + 1];
+
+    for (i, &x) in xs.iter().enumerate() {
+        for (j, &y) in ys.iter().enumerate() {
+            outside[i + 1][j + 1] =
+                outside[i][j + 1] + outside[i + 1][j] - outside[i][j] + usize::from(!inside(x, y));
+        }
+    }
+
+    let (mut p1, mut p2) = (0, 0);
+
+    for (k, a) in tiles.iter().enumerate() {
+        for b in &tiles[k + 1..] {
+            let surface = surface(a, b);
+            let position = |values: &[i64], value: i64| values.binary_search(&(value * 2)).unwrap();
+            let (x0, x1) = (position(&xs, a.x.min(b.x)), position(&xs, a.x.max(b.x)) + 1);
+            let (y0, y1) = (position(&ys, a.y.min(b.y)), position(&ys, a.y.max(b.y)) + 1);
+
+            p1 = p1.max(surface);
+            if outside[x1][y1] + outside[x0][y0] == outside[x0][y1] + outside[x1][y0] {
+                p2 = p2.max(surface);
+            }
+        }
+    }
+
+    (p1, p2)
+}
+
+#[test]
+fn check_grid() {
+    const TEST: &str = "7,1
+11,1
+11,7
+9,7
+9,5
+2,5
+2,3
+7,3";
+    use std::io::Cursor;
+
+    assert_eq!(resolve_grid(Cursor::new(TEST).lines()), (50, 24));
+}
+
+// Scale is the number of red tiles. The loop is made of columns side
+// by side whose bottoms and tops never overlap, all the coordinates
+// are multiples of 4 so that no edge is too short to be shifted
+// outward by one tile at both ends.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    let columns = (scale / 4).max(1);
+    let mut xs = vec![rng.range(0..=250) * 4];
+
+    for _ in 0..columns {
+        let x = xs[xs.len() - 1] + rng.range(1..=500) * 4;
+
+        xs.push(x);
+    }
+
+    let mut bottoms: Vec<u64> = vec![];
+    let mut tops: Vec<u64> = vec![];
+
+    for _ in 0..columns {
+        let (mut bottom, mut top) = (rng.range(0..=12_499) * 4, rng.range(12_501..=25_000) * 4);
+
+        // Consecutive vertices must not be aligned.
+        while bottoms.last() == Some(&bottom) {
+            bottom = rng.range(0..=12_499) * 4;
+        }
+        while tops.last() == Some(&top) {
+            top = rng.range(12_501..=25_000) * 4;
+        }
+
+        bottoms.push(bottom);
+        tops.push(top);
+    }
+
+    let mut tiles = String::new();
+
+    for i in 0..columns {
+        tiles += &format!("{},{}\n{},{}\n", xs[i], bottoms[i], xs[i + 1], bottoms[i]);
+    }
+    for i in (0..columns).rev() {
+        tiles += &format!("{},{}\n{},{}\n", xs[i + 1], tops[i], xs[i], tops[i]);
+    }
+
+    tiles
+}
+
+#[test]
+fn check_generate() {
+    use std::io::Cursor;
+
+    let input = generate(&mut Rng::new(1), 40);
+    let (p1, p2) = resolve(Cursor::new(&input).lines());
+
+    assert_eq!(input.lines().count(), 40);
+    assert!(p2 <= p1);
+
+    let input = generate(&mut Rng::new(1), 1);
+
+    assert_eq!(input.lines().count(), 4);
+    assert_eq!(
+        resolve(Cursor::new(&input).lines()).1,
+        resolve(Cursor::new(&input).lines()).0
+    );
+}
+
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
 where
     T: BufRead,
@@ -211,4 +337,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_grid_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_grid(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "grid", resolve_grid_string) }
@@ -1,3 +1,12 @@
+// The tests borrow the strings given to from_str.
+#![cfg_attr(test, allow(clippy::needless_borrow))]
+
+use advent_2025::parse::split_parse;
+use advent_2025::profile;
+use advent_2025::progress;
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
+use advent_2025::trace;
 use rayon::prelude::*;
 use std::collections::HashMap;
 use std::io::{BufRead, Lines};
@@ -23,21 +32,17 @@ impl FromStr for Button {
             return Err(ParseButtonError);
         }
 
-        let mut leds = vec![];
-        let mut mask = 0;
-
-        for &c in &bytes[1..len - 1] {
-            match c {
-                b',' => {}
-                b'0'..=b'9' => {
-                    let index = (c - b'0') as usize;
-
-                    leds.push(index);
-                    mask |= 1 << index;
-                }
-                _ => return Err(ParseButtonError),
-            }
-        }
+        let leds = split_parse::<usize>(&bytes[1..len - 1], b',')
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| ParseButtonError)?;
+        // The lights are a u32 mask.
+        let mask = leds
+            .iter()
+            .try_fold(0u32, |mask, &index| {
+                1u32.checked_shl(u32::try_from(index).ok()?)
+                    .map(|bit| mask | bit)
+            })
+            .ok_or(ParseButtonError)?;
 
         Ok(Button { mask, leds })
     }
@@ -58,6 +63,8 @@ fn check_parse_button() {
     let s = "(1,2,a,5)";
 
     assert_eq!(Button::from_str(&s), Err(ParseButtonError));
+    assert_eq!(Button::from_str("(31)").map(|b| b.mask), Ok(1 << 31));
+    assert_eq!(Button::from_str("(1,32)"), Err(ParseButtonError));
 }
 
 type Combinations = Vec<Vec<usize>>;
@@ -204,68 +211,287 @@ fn check_find_joules() {
     assert_eq!(Some(10), find_joules(&buttons, &joules, &mut cache));
 }
 
+type Machine = (u32, Vec<Button>, Vec<u32>);
+
+fn parse_machine(line: String) -> Machine {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+
+    let leds: u32 = words[0].as_bytes().iter().rev().fold(0, |acc, &b| match b {
+        b'.' => acc * 2,
+        b'#' => acc * 2 + 1,
+        _ => acc,
+    });
+    let buttons: Vec<Button> = words[1..words.len() - 1]
+        .iter()
+        .map(|s| Button::from_str(s).unwrap())
+        .collect();
+    let joltages = words[words.len() - 1].as_bytes();
+    let joltages: Vec<u32> = split_parse(&joltages[1..joltages.len() - 1], b',')
+        .collect::<Result<_, _>>()
+        .unwrap();
+
+    (leds, buttons, joltages)
+}
+
 fn resolve<T>(lines: Lines<T>) -> (usize, usize)
 where
     T: BufRead,
 {
     let machines = lines
-        .map(|line| {
-            let line = line.unwrap();
-            let words = line.split_whitespace().collect::<Vec<_>>();
-
-            let leds: u32 = words[0].as_bytes().iter().rev().fold(0, |acc, &b| match b {
-                b'.' => acc * 2,
-                b'#' => acc * 2 + 1,
-                _ => acc,
-            });
-            let buttons: Vec<Button> = words[1..words.len() - 1]
-                .iter()
-                .map(|s| Button::from_str(s).unwrap())
-                .collect();
-            let mut joltages: Vec<u32> = vec![0];
-
-            for &c in words[words.len() - 1].as_bytes() {
-                match c {
-                    b'0'..=b'9' => {
-                        *joltages.last_mut().unwrap() =
-                            10 * joltages.last().unwrap() + (c - b'0') as u32;
-                    }
-                    b',' => {
-                        joltages.push(0);
-                    }
-                    _ => {}
-                }
-            }
-
-            (leds, buttons, joltages)
-        })
+        .map(|line| parse_machine(line.unwrap()))
         .collect::<Vec<_>>();
 
+    lap("parse");
+    progress::total(machines.len(), "machines");
+
     machines
         .into_par_iter()
+        .enumerate()
         .fold(
             || (0, 0),
-            |(p1, p2), (leds, buttons, joltages)| {
+            |(p1, p2), (i, (leds, buttons, joltages))| {
+                let _task = profile::task("machine");
                 let mut cache = HashMap::new();
                 let combis = find_joltages_leds_cached(&buttons, leds, joltages.len(), &mut cache);
-
-                (
-                    p1 + combis.iter().map(|&(len, _)| len).min().unwrap_or_default(),
-                    p2 + find_joules(&buttons, &joltages, &mut cache).unwrap_or_default(),
-                )
+                let lights = combis
+                    .iter()
+                    .map(|&(len, _)| len)
+                    .min()
+                    .unwrap_or_else(|| panic!("machine {i}: no presses give the lights"));
+                let joules = find_joules(&buttons, &joltages, &mut cache)
+                    .unwrap_or_else(|| panic!("machine {i}: no presses give the joltages"));
+
+                trace!(
+                    Info,
+                    "machine {i}: {lights} presses for the lights, {joules} for the joltages"
+                );
+
+                progress::step();
+
+                (p1 + lights, p2 + joules)
             },
         )
         .reduce(|| (0, 0), |(a1, a2), (b1, b2)| (a1 + b1, a2 + b2))
 }
 
-#[test]
-fn check() {
-    const TEST: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a.abs()
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+// Solves the part 2 as a linear system: Gaussian elimination leaves a
+// few free buttons, all their bounded press counts are tried.
+fn find_joules_gauss(buttons: &[Button], joules: &[u32]) -> Option<usize> {
+    let (m, n) = (joules.len(), buttons.len());
+    let mut rows: Vec<Vec<i64>> = (0..m)
+        .map(|led| {
+            buttons
+                .iter()
+                .map(|b| b.leds.contains(&led) as i64)
+                .chain([joules[led] as i64])
+                .collect()
+        })
+        .collect();
+    let mut pivots = vec![];
+
+    for column in 0..n {
+        let r = pivots.len();
+
+        if r == m {
+            break;
+        }
+
+        let Some(p) = (r..m).find(|&i| rows[i][column] != 0) else {
+            continue;
+        };
+
+        rows.swap(r, p);
+
+        let pivot_row = rows[r].clone();
+
+        for (i, row) in rows.iter_mut().enumerate() {
+            if i != r && row[column] != 0 {
+                let (a, b) = (pivot_row[column], row[column]);
+
+                for (v, p) in row.iter_mut().zip(&pivot_row) {
+                    *v = *v * a - p * b;
+                }
+
+                let g = row.iter().fold(0, |g, &v| gcd(g, v));
+
+                if g > 1 {
+                    row.iter_mut().for_each(|v| *v /= g);
+                }
+            }
+        }
+
+        pivots.push(column);
+    }
+
+    if rows[pivots.len()..].iter().any(|row| row[n] != 0) {
+        // 0 = something
+        return None;
+    }
+
+    let free: Vec<usize> = (0..n).filter(|c| !pivots.contains(c)).collect();
+    let bounds: Vec<i64> = free
+        .iter()
+        .map(|&f| {
+            buttons[f]
+                .leds
+                .iter()
+                .map(|&led| joules[led] as i64)
+                .min()
+                .unwrap_or_default()
+        })
+        .collect();
+    let mut values = vec![0i64; free.len()];
+    let mut best = None;
+
+    loop {
+        let presses = pivots.iter().enumerate().try_fold(
+            values.iter().sum::<i64>(),
+            |total, (r, &column)| {
+                let row = &rows[r];
+                let rest = row[n]
+                    - free
+                        .iter()
+                        .zip(&values)
+                        .map(|(&f, &v)| row[f] * v)
+                        .sum::<i64>();
+
+                if rest % row[column] != 0 || rest / row[column] < 0 {
+                    None
+                } else {
+                    Some(total + rest / row[column])
+                }
+            },
+        );
+
+        if let Some(presses) = presses {
+            best = Some(best.map_or(presses, |b: i64| b.min(presses)));
+        }
+
+        // Next free values, like an odometer.
+        let Some(k) = (0..values.len()).find(|&k| values[k] < bounds[k]) else {
+            return best.map(|b| b as usize);
+        };
+
+        values[k] += 1;
+        values[..k].iter_mut().for_each(|v| *v = 0);
+    }
+}
+
+fn resolve_gauss<T>(lines: Lines<T>) -> (usize, usize)
+where
+    T: BufRead,
+{
+    lines
+        .map(|line| parse_machine(line.unwrap()))
+        .enumerate()
+        .fold((0, 0), |(p1, p2), (i, (leds, buttons, joltages))| {
+            assert!(
+                buttons.len() < u32::BITS as usize,
+                "machine {i}: too many buttons to try all their subsets"
+            );
+
+            // Part 1 by trying all the buttons subsets.
+            let presses = (0u32..1 << buttons.len())
+                .filter(|subset| {
+                    buttons
+                        .iter()
+                        .enumerate()
+                        .filter(|(index, _)| subset & (1 << index) != 0)
+                        .fold(0, |acc, (_, b)| acc ^ b.mask)
+                        == leds
+                })
+                .map(|subset| subset.count_ones() as usize)
+                .min()
+                .unwrap_or_else(|| panic!("machine {i}: no presses give the lights"));
+            let joules = find_joules_gauss(&buttons, &joltages)
+                .unwrap_or_else(|| panic!("machine {i}: no presses give the joltages"));
+
+            (p1 + presses, p2 + joules)
+        })
+}
+
+#[cfg(test)]
+const TEST: &str = "[.##.] (3) (1,3) (2) (2,3) (0,2) (0,1) {3,5,4,7}
 [...#.] (0,2,3,4) (2,3) (0,4) (0,1,2) (1,2,3,4) {7,5,12,7,2}
 [.###.#] (0,1,2,3,4) (0,3,4) (0,1,2,4,5) (1,2) {10,11,11,5,10,5}";
+
+#[test]
+fn check() {
     use std::io::Cursor;
 
     assert_eq!(resolve(Cursor::new(TEST).lines()), (7, 33));
+    assert_eq!(resolve_gauss(Cursor::new(TEST).lines()), (7, 33));
+}
+
+#[test]
+#[should_panic(expected = "machine 0: no presses give the lights")]
+fn check_unsolvable() {
+    use std::io::Cursor;
+
+    resolve(Cursor::new("[.#] (0) {1,0}").lines());
+}
+
+#[test]
+#[should_panic(expected = "machine 0: no presses give the lights")]
+fn check_unsolvable_gauss() {
+    use std::io::Cursor;
+
+    resolve_gauss(Cursor::new("[.#] (0) {1,0}").lines());
+}
+
+// Scale is the number of machines. Lights and joltages are obtained by
+// pressing random buttons so that every machine has a solution.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut machines = String::new();
+
+    for _ in 0..scale {
+        let leds = rng.range_usize(3..=10);
+        let buttons: Vec<Vec<usize>> = (0..rng.range_usize(leds - 2..=leds + 2))
+            .map(|_| {
+                let mut button: Vec<usize> = (0..leds).collect();
+
+                rng.shuffle(&mut button);
+                button.truncate(rng.range_usize(1..=leds));
+                button.sort_unstable();
+                button
+            })
+            .collect();
+        let mut lights = vec!['.'; leds];
+        let mut joltages = vec![0; leds];
+
+        for button in buttons.iter() {
+            let toggle = rng.chance(0.5);
+            let presses = rng.range(0..=15);
+
+            for &led in button.iter() {
+                if toggle {
+                    lights[led] = if lights[led] == '.' { '#' } else { '.' };
+                }
+                joltages[led] += presses;
+            }
+        }
+
+        machines += &format!("[{}]", lights.into_iter().collect::<String>());
+        for button in buttons {
+            let leds: Vec<String> = button.iter().map(usize::to_string).collect();
+
+            machines += &format!(" ({})", leds.join(","));
+        }
+
+        let joltages: Vec<String> = joltages.iter().map(u64::to_string).collect();
+
+        machines += &format!(" {{{}}}\n", joltages.join(","));
+    }
+
+    machines
 }
 
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
@@ -276,4 +502,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_gauss_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_gauss(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "gauss", resolve_gauss_string) }
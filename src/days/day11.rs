@@ -1,102 +1,98 @@
-use std::collections::HashMap;
+use advent_2025::graph::Graph;
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
+use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, Lines};
 
-fn letters_to_u32(letters: &[u8]) -> u32 {
-    letters.iter().fold(0, |acc, &c| (acc * 256) + c as u32)
+fn get_paths_nb(cables: &Graph, start: &str, end: &str) -> usize {
+    match (cables.id(start), cables.id(end)) {
+        // A cycle makes the count infinite, reported as no paths.
+        (Some(start), Some(end)) => cables.count_paths(start, end).map_or(0, |n| n as usize),
+        // One of the devices is not connected.
+        _ => 0,
+    }
 }
 
-fn word_to_u32(word: &str) -> u32 {
-    letters_to_u32(word.as_bytes())
-}
+fn resolve<T>(lines: Lines<T>) -> (usize, usize)
+where
+    T: BufRead,
+{
+    let mut cables = Graph::builder();
 
-#[cfg(test)]
-fn u32_to_string(n: u32) -> String {
-    std::str::from_utf8(&[16u32, 8, 0].map(|v| ((n >> v) & 0xff) as u8))
-        .unwrap()
-        .into()
-}
+    for line in lines {
+        let line = line.unwrap();
+        let (entry, outputs) = line.split_once(": ").expect("missing ':' separator");
 
-#[test]
-fn check_conversion() {
-    let n = word_to_u32("you");
+        for output in outputs.split_whitespace() {
+            cables.edge(entry, output);
+        }
+    }
 
-    assert_eq!(n, 0x796f75);
-    assert_eq!("you", u32_to_string(n));
-}
+    let cables = cables.build();
 
-fn get_paths_nb_cached(
-    cables: &HashMap<u32, Vec<u32>>,
-    start: u32,
-    end: u32,
-    cache: &mut HashMap<u32, Option<usize>>,
-) -> usize {
-    if let Some(entry) = cache.get(&start) {
-        // We're already know this path.
-        //
-        // If entry contains a Some(path), let's return path. If it's
-        // None it means we're cycling, so let's just break the cycle
-        // and return 0 (default).
-        entry.unwrap_or_default()
-    } else {
-        // First time we're here.
-        //
-        // Mark the cache with None. It means we're currently trying
-        // to resolve it. It is used as a hint to break cycle.
-        cache.insert(start, None);
-
-        let paths = if start == end {
-            1
-        } else if let Some(v) = cables.get(&start) {
-            v.iter()
-                .map(|&next| get_paths_nb_cached(cables, next, end, cache))
-                .sum()
-        } else {
-            // out?
-            0
-        };
+    lap("parse");
 
-        // The path is now resolved, let's update the cache.
-        cache.insert(start, Some(paths));
+    let p1 = get_paths_nb(&cables, "you", "out");
 
-        paths
-    }
-}
+    lap("part1");
+
+    let devices = ["svr", "fft", "dac", "out"];
+    let p2 = devices
+        .iter()
+        .zip(devices.iter().skip(1))
+        .map(|(a, b)| get_paths_nb(&cables, a, b))
+        .product::<usize>();
 
-fn get_paths_nb(cables: &HashMap<u32, Vec<u32>>, start: u32, end: u32) -> usize {
-    get_paths_nb_cached(cables, start, end, &mut HashMap::new())
+    lap("part2");
+
+    (p1, p2)
 }
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
+// Memoized DFS directly on the device names.
+fn resolve_dfs<T>(lines: Lines<T>) -> (usize, usize)
 where
     T: BufRead,
 {
-    let mut cables = HashMap::new();
-
-    for line in lines {
-        let line = line.unwrap();
-        let bytes = line.as_bytes();
-
-        let entry = letters_to_u32(&bytes[0..3]);
-        let outputs: Vec<u32> = bytes[5..]
-            .split(|&c| c == b' ')
-            .map(letters_to_u32)
-            .collect();
+    fn count<'a>(
+        cables: &'a HashMap<String, Vec<String>>,
+        from: &'a str,
+        to: &str,
+        cache: &mut HashMap<&'a str, usize>,
+    ) -> usize {
+        if from == to {
+            return 1;
+        }
+        if let Some(&paths) = cache.get(from) {
+            return paths;
+        }
+
+        let paths = cables.get(from).map_or(0, |outputs| {
+            outputs
+                .iter()
+                .map(|output| count(cables, output, to, cache))
+                .sum()
+        });
 
-        cables.insert(entry, outputs);
+        cache.insert(from, paths);
+        paths
     }
 
-    let devices: Vec<u32> = ["svr", "fft", "dac", "out"]
-        .into_iter()
-        .map(word_to_u32)
+    let cables: HashMap<String, Vec<String>> = lines
+        .map(|line| {
+            let line = line.unwrap();
+            let (entry, outputs) = line.split_once(": ").expect("missing ':' separator");
+
+            (
+                entry.to_string(),
+                outputs.split_whitespace().map(String::from).collect(),
+            )
+        })
         .collect();
+    let paths = |from, to| count(&cables, from, to, &mut HashMap::new());
 
     (
-        get_paths_nb(&cables, word_to_u32("you"), word_to_u32("out")),
-        devices
-            .iter()
-            .zip(devices.iter().skip(1))
-            .map(|(&a, &b)| get_paths_nb(&cables, a, b))
-            .product::<usize>(),
+        paths("you", "out"),
+        paths("svr", "fft") * paths("fft", "dac") * paths("dac", "out"),
     )
 }
 
@@ -115,6 +111,8 @@ iii: out";
     use std::io::Cursor;
 
     assert_eq!(resolve(Cursor::new(TEST).lines()), (5, 0));
+
+    assert_eq!(resolve_dfs(Cursor::new(TEST).lines()), (5, 0));
 }
 
 #[test]
@@ -135,6 +133,94 @@ hhh: out";
     use std::io::Cursor;
 
     assert_eq!(resolve(Cursor::new(TEST2).lines()), (0, 2));
+    assert_eq!(resolve_dfs(Cursor::new(TEST2).lines()), (0, 2));
+}
+
+#[test]
+fn check_cycle() {
+    const TEST: &str = "you: aaa
+aaa: you out";
+    use std::io::Cursor;
+
+    assert_eq!(resolve(Cursor::new(TEST).lines()), (0, 0));
+}
+
+// Scale is the number of devices. They are spread on layers only
+// connected to the next one, which keeps the numbers of paths small.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    const LAYERS: usize = 18;
+
+    let width = (scale / LAYERS).max(1);
+    let mut names = HashSet::from(["you", "svr", "fft", "dac", "out"].map(String::from));
+    // Three letters like the real input, longer names when at least
+    // half of the three letter ones would be needed.
+    let length = (3..)
+        .find(|&n| 26usize.pow(n) >= 2 * (LAYERS * width + names.len()))
+        .unwrap();
+    let mut layers: Vec<Vec<String>> = (0..LAYERS)
+        .map(|_| {
+            (0..width)
+                .map(|_| loop {
+                    let name: String = (0..length)
+                        .map(|_| (b'a' + rng.range(0..=25) as u8) as char)
+                        .collect();
+
+                    if names.insert(name.clone()) {
+                        break name;
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    layers[0][0] = "svr".to_string();
+    layers[2][width / 2] = "you".to_string();
+    layers[LAYERS / 3][0] = "fft".to_string();
+    layers[2 * LAYERS / 3][0] = "dac".to_string();
+
+    let mut lines = vec![];
+
+    for (i, layer) in layers.iter().enumerate() {
+        for device in layer {
+            let outputs = if i == LAYERS - 1 {
+                vec!["out"]
+            } else {
+                let mut next: Vec<&str> = layers[i + 1].iter().map(String::as_str).collect();
+
+                rng.shuffle(&mut next);
+                next.truncate(rng.range_usize(1..=3.min(width)));
+
+                // The first devices of each layer make a path going
+                // through svr, fft and dac.
+                if device == &layer[0] && !next.contains(&layers[i + 1][0].as_str()) {
+                    next[0] = &layers[i + 1][0];
+                }
+
+                next
+            };
+
+            lines.push(format!("{device}: {}\n", outputs.join(" ")));
+        }
+    }
+
+    rng.shuffle(&mut lines);
+    lines.concat()
+}
+
+#[test]
+fn check_generate() {
+    use std::io::Cursor;
+
+    let input = generate(&mut Rng::new(1), 200);
+    let (p1, p2) = resolve(Cursor::new(&input).lines());
+
+    assert!(p1 > 0);
+    assert!(p2 > 0);
+
+    // More devices than three letter names.
+    let input = generate(&mut Rng::new(1), 20_000);
+
+    assert_eq!(input.lines().count(), 18 * (20_000 / 18));
 }
 
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
@@ -145,4 +231,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_dfs_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_dfs(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "dfs", resolve_dfs_string) }
@@ -1,49 +1,64 @@
+use advent_2025::parse::integers;
+use advent_2025::progress;
+use advent_2025::random::Rng;
+use advent_2025::timing::lap;
+use advent_2025::Paragrapher;
+use std::error::Error;
 use std::io::{BufRead, Lines};
 
-fn resolve<T>(lines: Lines<T>) -> (usize, usize)
-where
-    T: BufRead,
-{
-    let mut shapes = vec![];
-    let mut lines = lines;
-
-    for _ in 0..6 {
-        let mut shape = 0;
-
-        lines.next().unwrap().unwrap();
-
-        for _ in 0..3 {
-            let line = lines.next().unwrap().unwrap();
-
-            shape += line
-                .as_bytes()
-                .iter()
-                .fold(0, |acc, &c| acc + if c == b'#' { 1 } else { 0 });
-        }
+struct Region {
+    width: usize,
+    height: usize,
+    counts: Vec<usize>,
+}
 
-        lines.next().unwrap().unwrap();
+fn parse_region(line: &str) -> Result<Region, Box<dyn Error>> {
+    let values = integers::<usize>(line.as_bytes()).collect::<Result<Vec<_>, _>>()?;
 
-        shapes.push(shape);
+    if values.len() < 2 {
+        return Err(format!("invalid region {line:?}").into());
     }
 
-    let mut p1 = 0;
-
-    for line in lines {
-        let line = line.unwrap();
-        let mut values = line.split_whitespace();
-        let dimensions = values
-            .next()
-            .unwrap()
-            .split(&['x', ':'])
-            .filter_map(|number| number.parse::<usize>().ok())
-            .collect::<Vec<_>>();
+    Ok(Region {
+        width: values[0],
+        height: values[1],
+        counts: values[2..].to_vec(),
+    })
+}
 
-        assert_eq!(dimensions.len(), 2);
+fn resolve<T>(lines: Lines<T>) -> (usize, usize)
+where
+    T: BufRead,
+{
+    let (shapes, regions) = lines
+        .two_sections(
+            |p| p[0].ends_with(':'),
+            |p| {
+                Ok(p[1..]
+                    .iter()
+                    .map(|line| line.bytes().filter(|&c| c == b'#').count())
+                    .sum::<usize>())
+            },
+            |line| parse_region(&line),
+        )
+        .unwrap_or_else(|e| panic!("{e}"));
+
+    lap("parse");
+    progress::total(regions.len(), "regions");
 
-        let (w, h) = (dimensions[0], dimensions[1]);
+    let mut p1 = 0;
 
-        let (surface, total_count) = values
-            .map(|s| s.parse::<usize>().unwrap())
+    for Region {
+        width: w,
+        height: h,
+        counts,
+    } in regions
+    {
+        assert!(counts.len() <= shapes.len(), "unknown shape in region");
+        progress::step();
+
+        let (surface, total_count) = counts
+            .into_iter()
             .enumerate()
             .fold((0, 0), |(s, t_c), (index, count)| {
                 (s + count * shapes[index], t_c + count)
@@ -64,6 +79,8 @@ where
         }
     }
 
+    lap("part1");
+
     (p1, 0)
 }
 
@@ -107,6 +124,109 @@ fn check() {
     assert_eq!(resolve(Cursor::new(TEST).lines()), (2, 0));
 }
 
+// Reference splitting the input as text, and laying the presents on
+// the region one per 3x3 slot.
+fn resolve_slots<T>(lines: Lines<T>) -> (usize, usize)
+where
+    T: BufRead,
+{
+    let input: String = lines.map(|line| line.unwrap() + "\n").collect();
+    let (shapes, regions) = input.rsplit_once("\n\n").expect("no regions");
+    let surfaces: Vec<usize> = shapes
+        .split("\n\n")
+        .map(|shape| shape.matches('#').count())
+        .collect();
+    let mut p1 = 0;
+
+    for region in regions.lines() {
+        let (size, counts) = region.split_once(": ").expect("invalid region");
+        let (width, height) = size.split_once('x').expect("invalid region");
+        let (width, height): (usize, usize) = (width.parse().unwrap(), height.parse().unwrap());
+        let counts: Vec<usize> = counts
+            .split_whitespace()
+            .map(|count| count.parse().unwrap())
+            .collect();
+        let mut slots = vec![vec![false; width / 3]; height / 3];
+        let mut presents = counts.iter().sum::<usize>();
+
+        for slot in slots.iter_mut().flatten() {
+            if presents > 0 {
+                *slot = true;
+                presents -= 1;
+            }
+        }
+
+        let surface: usize = counts.iter().zip(&surfaces).map(|(c, s)| c * s).sum();
+
+        if presents == 0 {
+            p1 += 1;
+        } else {
+            assert!(surface > width * height, "can't decide {region:?}");
+        }
+    }
+
+    (p1, 0)
+}
+
+// Scale is the number of regions. Presents either trivially fit in
+// 3x3 slots or cover more than the region, the solver can't decide the
+// other cases.
+fn generate(rng: &mut Rng, scale: usize) -> String {
+    let mut surfaces = vec![];
+    let mut input = String::new();
+
+    for index in 0..6 {
+        let mut shape: Vec<char> = (0..9)
+            .map(|_| if rng.chance(0.7) { '#' } else { '.' })
+            .collect();
+
+        shape[4] = '#';
+        surfaces.push(shape.iter().filter(|&&c| c == '#').count());
+
+        input += &format!("{index}:\n");
+        for row in shape.chunks(3) {
+            input += &format!("{}\n", row.iter().collect::<String>());
+        }
+        input.push('\n');
+    }
+
+    for _ in 0..scale {
+        let (width, height) = (rng.range_usize(5..=50), rng.range_usize(5..=50));
+        let mut counts = [0; 6];
+
+        if rng.chance(0.5) {
+            for _ in 0..rng.range_usize(0..=(width / 3) * (height / 3)) {
+                counts[rng.range_usize(0..=5)] += 1;
+            }
+        } else {
+            let mut surface = 0;
+
+            while surface <= width * height {
+                let index = rng.range_usize(0..=5);
+
+                counts[index] += 1;
+                surface += surfaces[index];
+            }
+        }
+
+        let counts: Vec<String> = counts.iter().map(usize::to_string).collect();
+
+        input += &format!("{width}x{height}: {}\n", counts.join(" "));
+    }
+
+    input
+}
+
+#[test]
+fn check_generate() {
+    use std::io::Cursor;
+
+    let input = generate(&mut Rng::new(1), 100);
+    let (p1, _) = resolve(Cursor::new(input).lines());
+
+    assert!(p1 > 25 && p1 < 75);
+}
+
 fn resolve_string<T>(lines: Lines<T>) -> (String, String)
 where
     T: BufRead,
@@ -115,4 +235,14 @@ where
     (solution.0.to_string(), solution.1.to_string())
 }
 
+fn resolve_slots_string<T>(lines: Lines<T>) -> (String, String)
+where
+    T: BufRead,
+{
+    let solution = resolve_slots(lines);
+    (solution.0.to_string(), solution.1.to_string())
+}
+
 inventory::submit! { advent_2025::Day::new(file!(), resolve_string) }
+inventory::submit! { advent_2025::Generator::new(file!(), generate) }
+inventory::submit! { advent_2025::Variant::new(file!(), "slots", resolve_slots_string) }
@@ -0,0 +1,521 @@
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+/// Numbers usable as coordinates.
+pub trait Coordinate:
+    Copy + Ord + Default + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ONE: Self;
+
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                const ONE: Self = 1;
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+fn abs_diff<T>(a: T, b: T) -> T
+where
+    T: Coordinate,
+{
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Orientation {
+    Clockwise,
+    CounterClockwise,
+    Collinear,
+}
+
+/// A 2D point, also used as a vector. Orientations assume the y axis
+/// is pointing up.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point2<T>
+where
+    T: Coordinate,
+{
+    pub const fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    pub fn square_distance(&self, other: &Self) -> T {
+        let (dx, dy) = (abs_diff(self.x, other.x), abs_diff(self.y, other.y));
+
+        dx * dx + dy * dy
+    }
+
+    pub fn euclidean_distance(&self, other: &Self) -> f64 {
+        self.square_distance(other).to_f64().sqrt()
+    }
+
+    /// Z component of the cross product of two vectors.
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl<T> Point2<T>
+where
+    T: Coordinate + Neg<Output = T>,
+{
+    /// Vector with each component replaced by its sign.
+    pub fn signum(&self) -> Self {
+        let signum = |v: T| {
+            if v > T::default() {
+                T::ONE
+            } else if v < T::default() {
+                -T::ONE
+            } else {
+                T::default()
+            }
+        };
+
+        Point2::new(signum(self.x), signum(self.y))
+    }
+}
+
+/// Orientation of the turn `a` -> `b` -> `c`.
+pub fn orientation<T>(a: &Point2<T>, b: &Point2<T>, c: &Point2<T>) -> Orientation
+where
+    T: Coordinate + Neg<Output = T>,
+{
+    let cross = (*b - *a).cross(&(*c - *b));
+
+    if cross > T::default() {
+        Orientation::CounterClockwise
+    } else if cross < T::default() {
+        Orientation::Clockwise
+    } else {
+        Orientation::Collinear
+    }
+}
+
+impl<T> Add for Point2<T>
+where
+    T: Coordinate,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point2::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T> Sub for Point2<T>
+where
+    T: Coordinate,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point2::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T> Mul<T> for Point2<T>
+where
+    T: Coordinate,
+{
+    type Output = Self;
+
+    fn mul(self, k: T) -> Self {
+        Point2::new(self.x * k, self.y * k)
+    }
+}
+
+impl<T> Neg for Point2<T>
+where
+    T: Coordinate + Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point2::new(-self.x, -self.y)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Point3<T>
+where
+    T: Coordinate,
+{
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+
+    pub fn manhattan_distance(&self, other: &Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y) + abs_diff(self.z, other.z)
+    }
+
+    pub fn square_distance(&self, other: &Self) -> T {
+        let (dx, dy, dz) = (
+            abs_diff(self.x, other.x),
+            abs_diff(self.y, other.y),
+            abs_diff(self.z, other.z),
+        );
+
+        dx * dx + dy * dy + dz * dz
+    }
+
+    pub fn euclidean_distance(&self, other: &Self) -> f64 {
+        self.square_distance(other).to_f64().sqrt()
+    }
+
+    pub fn cross(&self, other: &Self) -> Self {
+        Point3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl<T> Add for Point3<T>
+where
+    T: Coordinate,
+{
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl<T> Sub for Point3<T>
+where
+    T: Coordinate,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct ParsePointError;
+
+impl fmt::Display for ParsePointError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid comma separated coordinates")
+    }
+}
+
+impl std::error::Error for ParsePointError {}
+
+fn parse_coordinates<T, const N: usize>(s: &str) -> Result<[T; N], ParsePointError>
+where
+    T: FromStr + Default + Copy,
+{
+    let mut coordinates = [T::default(); N];
+    let mut values = s.split(',');
+
+    for coordinate in coordinates.iter_mut() {
+        *coordinate = values
+            .next()
+            .ok_or(ParsePointError)?
+            .trim()
+            .parse()
+            .map_err(|_| ParsePointError)?;
+    }
+
+    if values.next().is_some() {
+        return Err(ParsePointError);
+    }
+
+    Ok(coordinates)
+}
+
+impl<T> FromStr for Point2<T>
+where
+    T: Coordinate + FromStr,
+{
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y] = parse_coordinates(s)?;
+
+        Ok(Point2::new(x, y))
+    }
+}
+
+impl<T> FromStr for Point3<T>
+where
+    T: Coordinate + FromStr,
+{
+    type Err = ParsePointError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let [x, y, z] = parse_coordinates(s)?;
+
+        Ok(Point3::new(x, y, z))
+    }
+}
+
+#[test]
+fn check_parse_point() {
+    assert_eq!("1,2,3".parse(), Ok(Point3::new(1, 2, 3)));
+    assert_eq!("1, 2, 3".parse(), Ok(Point3::new(1, 2, 3)));
+    assert_eq!("1,2".parse::<Point3<i64>>(), Err(ParsePointError));
+    assert_eq!("1,2,3".parse::<Point2<i64>>(), Err(ParsePointError));
+    assert_eq!("-7,1".parse(), Ok(Point2::new(-7, 1)));
+    assert_eq!("a,1".parse::<Point2<i64>>(), Err(ParsePointError));
+}
+
+#[test]
+fn check_distances() {
+    let (a, b) = (Point2::new(1u32, 5), Point2::new(4, 1));
+
+    assert_eq!(a.manhattan_distance(&b), 7);
+    assert_eq!(a.square_distance(&b), 25);
+    assert_eq!(a.euclidean_distance(&b), 5.0);
+
+    let (a, b) = (Point3::new(162i64, 817, 812), Point3::new(425, 690, 689));
+
+    assert_eq!(a.square_distance(&b), 100427);
+    assert_eq!(a.manhattan_distance(&b), 513);
+}
+
+#[test]
+fn check_orientation() {
+    let (a, b) = (Point2::new(0, 0), Point2::new(2, 0));
+
+    assert_eq!((b - a).cross(&Point2::new(0, 1)), 2);
+    assert_eq!(
+        orientation(&a, &b, &Point2::new(3, 1)),
+        Orientation::CounterClockwise
+    );
+    assert_eq!(
+        orientation(&a, &b, &Point2::new(3, -1)),
+        Orientation::Clockwise
+    );
+    assert_eq!(
+        orientation(&a, &b, &Point2::new(5, 0)),
+        Orientation::Collinear
+    );
+    assert_eq!(Point2::new(-4, 0).signum(), Point2::new(-1, 0));
+    assert_eq!(
+        Point3::new(1, 0, 0).cross(&Point3::new(0, 1, 0)),
+        Point3::new(0, 0, 1)
+    );
+}
+
+/// An axis-aligned rectangle, both corners included.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rect<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+impl<T> Rect<T>
+where
+    T: Coordinate,
+{
+    /// Builds the rectangle from any two opposite corners.
+    pub fn from_corners(a: &Point2<T>, b: &Point2<T>) -> Self {
+        Rect {
+            min: Point2::new(a.x.min(b.x), a.y.min(b.y)),
+            max: Point2::new(a.x.max(b.x), a.y.max(b.y)),
+        }
+    }
+
+    pub fn width(&self) -> T {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> T {
+        self.max.y - self.min.y
+    }
+
+    pub fn area(&self) -> T {
+        self.width() * self.height()
+    }
+
+    /// Number of grid cells covered, borders included.
+    pub fn cell_count(&self) -> T {
+        (self.width() + T::ONE) * (self.height() + T::ONE)
+    }
+
+    pub fn contains(&self, p: &Point2<T>) -> bool {
+        (self.min.x..=self.max.x).contains(&p.x) && (self.min.y..=self.max.y).contains(&p.y)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = Point2::new(self.min.x.max(other.min.x), self.min.y.max(other.min.y));
+        let max = Point2::new(self.max.x.min(other.max.x), self.max.y.min(other.max.y));
+
+        if min.x <= max.x && min.y <= max.y {
+            Some(Rect { min, max })
+        } else {
+            None
+        }
+    }
+
+    pub fn intersects(&self, other: &Self) -> bool {
+        self.intersection(other).is_some()
+    }
+}
+
+#[test]
+fn check_rect() {
+    let r = Rect::from_corners(&Point2::new(4, 1), &Point2::new(1, 3));
+
+    assert_eq!(r.min, Point2::new(1, 1));
+    assert_eq!(
+        (r.width(), r.height(), r.area(), r.cell_count()),
+        (3, 2, 6, 12)
+    );
+    assert!(r.contains(&Point2::new(4, 3)));
+    assert!(!r.contains(&Point2::new(5, 3)));
+
+    let other = Rect::from_corners(&Point2::new(4, 3), &Point2::new(8, 8));
+
+    assert_eq!(
+        r.intersection(&other),
+        Some(Rect::from_corners(&Point2::new(4, 3), &Point2::new(4, 3)))
+    );
+    assert!(!r.intersects(&Rect::from_corners(&Point2::new(5, 0), &Point2::new(6, 6))));
+}
+
+/// A simple polygon given by its vertices in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Polygon<T> {
+    pub vertices: Vec<Point2<T>>,
+}
+
+impl<T> Polygon<T>
+where
+    T: Coordinate + Neg<Output = T>,
+{
+    pub fn new(vertices: Vec<Point2<T>>) -> Self {
+        Polygon { vertices }
+    }
+
+    /// Iterates over the edges, the last one closing the polygon.
+    pub fn edges(&self) -> impl Iterator<Item = (&Point2<T>, &Point2<T>)> {
+        self.vertices
+            .iter()
+            .zip(self.vertices.iter().cycle().skip(1))
+    }
+
+    /// Twice the signed area (shoelace formula), positive for
+    /// counter clockwise polygons.
+    pub fn double_signed_area(&self) -> T {
+        self.edges()
+            .fold(T::default(), |acc, (a, b)| acc + a.cross(b))
+    }
+
+    pub fn orientation(&self) -> Orientation {
+        let area = self.double_signed_area();
+
+        if area > T::default() {
+            Orientation::CounterClockwise
+        } else if area < T::default() {
+            Orientation::Clockwise
+        } else {
+            Orientation::Collinear
+        }
+    }
+
+    /// Whether `p` is inside the polygon or on its boundary.
+    pub fn contains(&self, p: &Point2<T>) -> bool {
+        let mut inside = false;
+
+        for (a, b) in self.edges() {
+            let cross = (*b - *a).cross(&(*p - *a));
+
+            if cross == T::default() && Rect::from_corners(a, b).contains(p) {
+                return true;
+            }
+
+            if (a.y > p.y) != (b.y > p.y) {
+                // The edge crosses the horizontal line of p, check
+                // that p is on the left of the upward edge.
+                let upward = b.y > a.y;
+
+                if upward == (cross > T::default()) {
+                    inside = !inside;
+                }
+            }
+        }
+
+        inside
+    }
+}
+
+#[test]
+fn check_polygon() {
+    let polygon = Polygon::new(
+        [
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ]
+        .map(|(x, y)| Point2::new(x, y))
+        .to_vec(),
+    );
+
+    assert_eq!(polygon.edges().count(), 8);
+    assert_eq!(polygon.double_signed_area(), 2 * 30);
+    assert_eq!(polygon.orientation(), Orientation::CounterClockwise);
+
+    assert!(polygon.contains(&Point2::new(10, 2)));
+    assert!(polygon.contains(&Point2::new(7, 1)));
+    assert!(polygon.contains(&Point2::new(2, 4)));
+    assert!(polygon.contains(&Point2::new(9, 6)));
+    assert!(!polygon.contains(&Point2::new(8, 6)));
+    assert!(!polygon.contains(&Point2::new(3, 2)));
+    assert!(!polygon.contains(&Point2::new(12, 4)));
+
+    let reversed = Polygon::new(polygon.vertices.iter().rev().copied().collect());
+
+    assert_eq!(reversed.orientation(), Orientation::Clockwise);
+    assert!(reversed.contains(&Point2::new(10, 2)));
+    assert!(!reversed.contains(&Point2::new(8, 6)));
+}
@@ -0,0 +1,379 @@
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct CycleError;
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the graph contains a cycle")
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+#[derive(Default)]
+pub struct GraphBuilder {
+    ids: HashMap<String, NodeId>,
+    names: Vec<String>,
+    edges: Vec<(NodeId, NodeId)>,
+}
+
+impl GraphBuilder {
+    /// Returns the id of `name`, creating the node if needed.
+    pub fn node(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = NodeId(u32::try_from(self.names.len()).expect("too many nodes"));
+
+        self.ids.insert(name.to_string(), id);
+        self.names.push(name.to_string());
+
+        id
+    }
+
+    pub fn edge(&mut self, from: &str, to: &str) -> &mut Self {
+        let (from, to) = (self.node(from), self.node(to));
+
+        self.edges.push((from, to));
+        self
+    }
+
+    pub fn build(self) -> Graph {
+        let len = self.names.len();
+        let (offsets, targets) = compress(len, self.edges.iter().copied());
+        let (reverse_offsets, sources) =
+            compress(len, self.edges.iter().map(|&(from, to)| (to, from)));
+
+        Graph {
+            ids: self.ids,
+            names: self.names,
+            offsets,
+            targets,
+            reverse_offsets,
+            sources,
+        }
+    }
+}
+
+/// Builds the compressed adjacency: the successors of node `i` are
+/// `targets[offsets[i]..offsets[i + 1]]`.
+fn compress<I>(len: usize, edges: I) -> (Vec<usize>, Vec<NodeId>)
+where
+    I: Iterator<Item = (NodeId, NodeId)> + Clone,
+{
+    let mut offsets = vec![0; len + 1];
+
+    for (from, _) in edges.clone() {
+        offsets[from.index() + 1] += 1;
+    }
+    for i in 0..len {
+        offsets[i + 1] += offsets[i];
+    }
+
+    let mut next = offsets.clone();
+    let mut targets = vec![NodeId(0); offsets[len]];
+
+    for (from, to) in edges {
+        targets[next[from.index()]] = to;
+        next[from.index()] += 1;
+    }
+
+    (offsets, targets)
+}
+
+/// A directed graph with string named nodes.
+pub struct Graph {
+    ids: HashMap<String, NodeId>,
+    names: Vec<String>,
+    offsets: Vec<usize>,
+    targets: Vec<NodeId>,
+    /// Same for the predecessors.
+    reverse_offsets: Vec<usize>,
+    sources: Vec<NodeId>,
+}
+
+impl Graph {
+    pub fn builder() -> GraphBuilder {
+        GraphBuilder::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id.index()]
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> + Clone {
+        (0..self.names.len() as u32).map(NodeId)
+    }
+
+    pub fn successors(&self, id: NodeId) -> &[NodeId] {
+        &self.targets[self.offsets[id.index()]..self.offsets[id.index() + 1]]
+    }
+
+    pub fn predecessors(&self, id: NodeId) -> &[NodeId] {
+        &self.sources[self.reverse_offsets[id.index()]..self.reverse_offsets[id.index() + 1]]
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (NodeId, NodeId)> + Clone + '_ {
+        self.nodes()
+            .flat_map(move |from| self.successors(from).iter().map(move |&to| (from, to)))
+    }
+
+    /// The same graph with all the edges reversed.
+    pub fn reversed(&self) -> Graph {
+        Graph {
+            ids: self.ids.clone(),
+            names: self.names.clone(),
+            offsets: self.reverse_offsets.clone(),
+            targets: self.sources.clone(),
+            reverse_offsets: self.offsets.clone(),
+            sources: self.targets.clone(),
+        }
+    }
+
+    /// Marks the nodes reachable from `start`, `start` included.
+    pub fn reachable(&self, start: NodeId) -> Vec<bool> {
+        self.walk(start, |id| self.successors(id))
+    }
+
+    /// Marks the nodes reaching `end`, `end` included.
+    pub fn reaching(&self, end: NodeId) -> Vec<bool> {
+        self.walk(end, |id| self.predecessors(id))
+    }
+
+    fn walk<'a>(&'a self, start: NodeId, next: impl Fn(NodeId) -> &'a [NodeId]) -> Vec<bool> {
+        let mut seen = vec![false; self.len()];
+        let mut stack = vec![start];
+
+        seen[start.index()] = true;
+
+        while let Some(id) = stack.pop() {
+            for &next in next(id) {
+                if !seen[next.index()] {
+                    seen[next.index()] = true;
+                    stack.push(next);
+                }
+            }
+        }
+
+        seen
+    }
+
+    /// Kahn's algorithm restricted to the nodes kept by `keep`.
+    fn topological_sort_of(&self, keep: &[bool]) -> Result<Vec<NodeId>, CycleError> {
+        let mut in_degrees = vec![0usize; self.len()];
+
+        for (from, to) in self.edges() {
+            if keep[from.index()] && keep[to.index()] {
+                in_degrees[to.index()] += 1;
+            }
+        }
+
+        let mut queue: VecDeque<NodeId> = self
+            .nodes()
+            .filter(|id| keep[id.index()] && in_degrees[id.index()] == 0)
+            .collect();
+        let mut order = vec![];
+
+        while let Some(id) = queue.pop_front() {
+            order.push(id);
+
+            for &next in self.successors(id) {
+                if keep[next.index()] {
+                    in_degrees[next.index()] -= 1;
+
+                    if in_degrees[next.index()] == 0 {
+                        queue.push_back(next);
+                    }
+                }
+            }
+        }
+
+        if order.len() == keep.iter().filter(|&&k| k).count() {
+            Ok(order)
+        } else {
+            Err(CycleError)
+        }
+    }
+
+    pub fn topological_sort(&self) -> Result<Vec<NodeId>, CycleError> {
+        self.topological_sort_of(&vec![true; self.len()])
+    }
+
+    /// Returns the nodes of a cycle if there is one, the first node
+    /// being the successor of the last one.
+    pub fn find_cycle(&self) -> Option<Vec<NodeId>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum State {
+            New,
+            InProgress,
+            Done,
+        }
+
+        let mut states = vec![State::New; self.len()];
+
+        for start in self.nodes() {
+            if states[start.index()] != State::New {
+                continue;
+            }
+
+            // Iterative DFS, the stack holds the current path and the
+            // index of the next successor to visit.
+            let mut stack = vec![(start, 0)];
+
+            states[start.index()] = State::InProgress;
+
+            while let Some((id, next)) = stack.last_mut() {
+                let id = *id;
+
+                if let Some(&successor) = self.successors(id).get(*next) {
+                    *next += 1;
+
+                    match states[successor.index()] {
+                        State::New => {
+                            states[successor.index()] = State::InProgress;
+                            stack.push((successor, 0));
+                        }
+                        State::InProgress => {
+                            let position =
+                                stack.iter().position(|&(id, _)| id == successor).unwrap();
+
+                            return Some(stack[position..].iter().map(|&(id, _)| id).collect());
+                        }
+                        State::Done => {}
+                    }
+                } else {
+                    states[id.index()] = State::Done;
+                    stack.pop();
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Counts the paths going from `from` to `to`. Fails if a cycle
+    /// makes this number infinite.
+    pub fn count_paths(&self, from: NodeId, to: NodeId) -> Result<u64, CycleError> {
+        let forward = self.reachable(from);
+        let backward = self.reaching(to);
+        let keep: Vec<bool> = forward
+            .into_iter()
+            .zip(backward)
+            .map(|(f, b)| f && b)
+            .collect();
+        let mut paths = vec![0u64; self.len()];
+
+        paths[from.index()] = 1;
+
+        for id in self.topological_sort_of(&keep)? {
+            for &next in self.successors(id) {
+                if keep[next.index()] {
+                    paths[next.index()] += paths[id.index()];
+                }
+            }
+        }
+
+        Ok(if keep[to.index()] {
+            paths[to.index()]
+        } else {
+            0
+        })
+    }
+}
+
+#[cfg(test)]
+fn graph_test(edges: &[(&str, &str)]) -> Graph {
+    let mut builder = Graph::builder();
+
+    for (from, to) in edges {
+        builder.edge(from, to);
+    }
+
+    builder.build()
+}
+
+#[test]
+fn check_builder() {
+    let graph = graph_test(&[("a", "b"), ("a", "c"), ("c", "b"), ("d", "a")]);
+    let id = |name| graph.id(name).unwrap();
+
+    assert_eq!(graph.len(), 4);
+    assert_eq!(graph.name(id("c")), "c");
+    assert_eq!(graph.id("e"), None);
+    assert_eq!(graph.successors(id("a")), [id("b"), id("c")]);
+    assert_eq!(graph.successors(id("b")), []);
+    assert_eq!(graph.reversed().successors(id("b")), [id("a"), id("c")]);
+    assert_eq!(graph.predecessors(id("a")), [id("d")]);
+    assert_eq!(graph.reaching(id("b")), [true, true, true, true]);
+}
+
+#[test]
+fn check_topological_sort() {
+    let graph = graph_test(&[("a", "b"), ("a", "c"), ("c", "b"), ("d", "a")]);
+    let order: Vec<&str> = graph
+        .topological_sort()
+        .unwrap()
+        .into_iter()
+        .map(|id| graph.name(id))
+        .collect();
+
+    assert_eq!(order, ["d", "a", "c", "b"]);
+    assert_eq!(graph.find_cycle(), None);
+
+    let graph = graph_test(&[("a", "b"), ("b", "c"), ("c", "d"), ("d", "b")]);
+    let cycle: Vec<&str> = graph
+        .find_cycle()
+        .unwrap()
+        .into_iter()
+        .map(|id| graph.name(id))
+        .collect();
+
+    assert_eq!(graph.topological_sort(), Err(CycleError));
+    assert_eq!(cycle, ["b", "c", "d"]);
+}
+
+#[test]
+fn check_count_paths() {
+    let graph = graph_test(&[
+        ("a", "b"),
+        ("a", "c"),
+        ("b", "d"),
+        ("c", "d"),
+        ("d", "e"),
+        ("c", "e"),
+        // This cycle can't reach e.
+        ("c", "f"),
+        ("f", "g"),
+        ("g", "f"),
+    ]);
+    let id = |name| graph.id(name).unwrap();
+
+    assert_eq!(graph.count_paths(id("a"), id("e")), Ok(3));
+    assert_eq!(graph.count_paths(id("a"), id("a")), Ok(1));
+    assert_eq!(graph.count_paths(id("e"), id("a")), Ok(0));
+    assert_eq!(graph.count_paths(id("a"), id("g")), Err(CycleError));
+}
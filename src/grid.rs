@@ -0,0 +1,401 @@
+use std::fmt;
+use std::io::{self, BufRead, Lines};
+use std::ops::{Index, IndexMut};
+
+/// Offsets of the 4 orthogonal neighbors.
+const ORTHOGONALS: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+/// Offsets of the 8 neighbors, diagonals included.
+const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Connectivity {
+    Four,
+    Eight,
+}
+
+impl Connectivity {
+    fn offsets(self) -> &'static [(isize, isize)] {
+        match self {
+            Connectivity::Four => &ORTHOGONALS,
+            Connectivity::Eight => &ALL_DIRECTIONS,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum GridError {
+    Io(io::Error),
+    Width {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Io(error) => write!(f, "unable to read grid: {error}"),
+            GridError::Width {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has a different width than the first one ({found} instead of {expected})"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl From<io::Error> for GridError {
+    fn from(error: io::Error) -> Self {
+        GridError::Io(error)
+    }
+}
+
+/// A rectangular grid stored row by row, indexed with `(x, y)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        assert!(
+            width == 0 && cells.is_empty() || width != 0 && cells.len().is_multiple_of(width),
+            "{} cells can't be split in rows of {width}",
+            cells.len()
+        );
+
+        let height = cells.len().checked_div(width).unwrap_or_default();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.cells[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn row_mut(&mut self, y: usize) -> &mut [T] {
+        &mut self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + ExactSizeIterator {
+        // chunks_exact panics on a 0 chunk size, an empty grid has no rows.
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> + ExactSizeIterator {
+        assert!(x < self.width, "column {x} out of grid");
+
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Iterates over all the `(x, y)` positions, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: self.columns().flatten().cloned().collect(),
+        }
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Neighbors of `(x, y)` that are inside the grid.
+    pub fn neighbors(
+        &self,
+        x: usize,
+        y: usize,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+
+        connectivity.offsets().iter().filter_map(move |&(dx, dy)| {
+            let nx = x.checked_add_signed(dx)?;
+            let ny = y.checked_add_signed(dy)?;
+
+            if nx < width && ny < height {
+                Some((nx, ny))
+            } else {
+                None
+            }
+        })
+    }
+
+    /// Neighbors of `(x, y)`, the grid being considered as a torus. An
+    /// empty grid has none.
+    pub fn neighbors_wrapping(
+        &self,
+        x: usize,
+        y: usize,
+        connectivity: Connectivity,
+    ) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        let offsets = if self.cells.is_empty() {
+            &[]
+        } else {
+            connectivity.offsets()
+        };
+
+        offsets.iter().map(move |&(dx, dy)| {
+            (
+                (x as isize + dx).rem_euclid(width) as usize,
+                (y as isize + dy).rem_euclid(height) as usize,
+            )
+        })
+    }
+}
+
+impl Grid<u8> {
+    /// Builds a grid of bytes, one row per line. All lines must have
+    /// the same width.
+    pub fn from_lines<B>(lines: Lines<B>) -> Result<Self, GridError>
+    where
+        B: BufRead,
+    {
+        let mut width = None;
+        let mut cells = vec![];
+
+        for (y, line) in lines.enumerate() {
+            let line = line?;
+            let expected = *width.get_or_insert(line.len());
+
+            if line.len() != expected {
+                return Err(GridError::Width {
+                    line: y + 1,
+                    expected,
+                    found: line.len(),
+                });
+            }
+
+            cells.extend_from_slice(line.as_bytes());
+        }
+
+        Ok(Grid::from_vec(width.unwrap_or_default(), cells))
+    }
+
+    /// Position of the first `byte` found, row by row.
+    pub fn find(&self, byte: u8) -> Option<(usize, usize)> {
+        self.cells
+            .iter()
+            .position(|&c| c == byte)
+            .map(|index| (index % self.width, index / self.width))
+    }
+
+    pub fn find_all(&self, byte: u8) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.iter()
+            .filter(move |&(_, &c)| c == byte)
+            .map(|(position, _)| position)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(x < self.width && y < self.height, "({x}, {y}) out of grid");
+
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(x < self.width && y < self.height, "({x}, {y}) out of grid");
+
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+impl fmt::Display for Grid<u8> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.rows() {
+            for &c in row {
+                write!(f, "{}", c as char)?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+fn grid_test(s: &str) -> Grid<u8> {
+    use std::io::Cursor;
+
+    Grid::from_lines(Cursor::new(s).lines()).unwrap()
+}
+
+#[test]
+fn check_from_lines() {
+    let grid = grid_test("abc\ndef");
+
+    assert_eq!((grid.width(), grid.height()), (3, 2));
+    assert_eq!(grid[(0, 0)], b'a');
+    assert_eq!(grid[(2, 1)], b'f');
+    assert_eq!(grid.get(3, 0), None);
+    assert_eq!(grid.get(0, 2), None);
+    assert_eq!(grid.to_string(), "abc\ndef\n");
+
+    let grid = grid_test("");
+
+    assert_eq!((grid.width(), grid.height()), (0, 0));
+    assert_eq!(grid.rows().count(), 0);
+
+    use std::io::Cursor;
+
+    let error = Grid::from_lines(Cursor::new("abc\nde\nfgh").lines()).unwrap_err();
+
+    assert!(matches!(
+        error,
+        GridError::Width {
+            line: 2,
+            expected: 3,
+            found: 2
+        }
+    ));
+}
+
+#[test]
+fn check_views() {
+    let grid = grid_test("abc\ndef");
+
+    assert_eq!(grid.row(1), b"def");
+    assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), b"be");
+    assert_eq!(
+        grid.columns()
+            .map(|c| c.copied().collect::<Vec<_>>())
+            .collect::<Vec<_>>(),
+        [b"ad", b"be", b"cf"]
+    );
+
+    let transposed = grid.transpose();
+
+    assert_eq!((transposed.width(), transposed.height()), (2, 3));
+    assert_eq!(transposed.to_string(), "ad\nbe\ncf\n");
+    assert_eq!(transposed.transpose(), grid);
+}
+
+#[test]
+fn check_find() {
+    let grid = grid_test("..#\n#.S");
+
+    assert_eq!(grid.find(b'S'), Some((2, 1)));
+    assert_eq!(grid.find(b'X'), None);
+    assert_eq!(grid.find_all(b'#').collect::<Vec<_>>(), [(2, 0), (0, 1)]);
+}
+
+#[test]
+fn check_neighbors() {
+    let grid = Grid::new(3, 3, 0u8);
+
+    assert_eq!(
+        grid.neighbors(0, 0, Connectivity::Four).collect::<Vec<_>>(),
+        [(1, 0), (0, 1)]
+    );
+    assert_eq!(grid.neighbors(1, 1, Connectivity::Four).count(), 4);
+    assert_eq!(
+        grid.neighbors(0, 0, Connectivity::Eight)
+            .collect::<Vec<_>>(),
+        [(1, 0), (0, 1), (1, 1)]
+    );
+    assert_eq!(grid.neighbors(1, 1, Connectivity::Eight).count(), 8);
+    assert_eq!(grid.neighbors(2, 2, Connectivity::Eight).count(), 3);
+    assert_eq!(
+        grid.neighbors_wrapping(0, 0, Connectivity::Four)
+            .collect::<Vec<_>>(),
+        [(0, 2), (2, 0), (1, 0), (0, 1)]
+    );
+    assert_eq!(
+        grid.neighbors_wrapping(0, 0, Connectivity::Eight).count(),
+        8
+    );
+    assert_eq!(
+        Grid::new(0, 3, 0u8)
+            .neighbors_wrapping(0, 0, Connectivity::Four)
+            .count(),
+        0
+    );
+}
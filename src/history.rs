@@ -0,0 +1,269 @@
+use crate::report::Report;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::process::Command;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+pub const HISTORY_FILE: &str = "./history.tsv";
+
+/// Timing of one day in a past run. The history file has one tab
+/// separated line per entry.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+    pub revision: String,
+    pub host: String,
+    pub day_number: u32,
+    pub duration: Duration,
+    /// Peak heap usage, when the allocations were counted.
+    pub peak: Option<usize>,
+}
+
+impl Entry {
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.revision,
+            self.host,
+            self.day_number,
+            self.duration.as_nanos(),
+            self.peak.map_or("-".to_string(), |peak| peak.to_string())
+        )
+    }
+
+    fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let entry = Entry {
+            timestamp: fields.next()?.parse().ok()?,
+            revision: fields.next()?.to_string(),
+            host: fields.next()?.to_string(),
+            day_number: fields.next()?.parse().ok()?,
+            duration: Duration::from_nanos(fields.next()?.parse().ok()?),
+            // Missing in the first versions of the file.
+            peak: fields.next().and_then(|peak| peak.parse().ok()),
+        };
+
+        fields.next().is_none().then_some(entry)
+    }
+}
+
+fn git_revision() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok())
+        .map(|rev| rev.trim().to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+#[cfg(unix)]
+fn host_name() -> String {
+    let mut buffer = [0u8; 256];
+
+    // SAFETY: the buffer is large enough for a host name and its length
+    // is given.
+    if unsafe { libc::gethostname(buffer.as_mut_ptr().cast(), buffer.len()) } != 0 {
+        return "-".to_string();
+    }
+
+    let len = buffer.iter().position(|&c| c == 0).unwrap_or(buffer.len());
+
+    String::from_utf8_lossy(&buffer[..len]).into_owned()
+}
+
+#[cfg(not(unix))]
+fn host_name() -> String {
+    std::env::var("COMPUTERNAME").unwrap_or_else(|_| "-".to_string())
+}
+
+/// Appends the days of a run to the history file.
+pub fn record(path: &Path, report: &Report) -> io::Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let (revision, host) = (git_revision(), host_name());
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    for day in report.days.iter() {
+        let entry = Entry {
+            timestamp,
+            revision: revision.clone(),
+            host: host.clone(),
+            day_number: day.day_number,
+            duration: day.duration,
+            peak: day.memory.map(|memory| memory.peak),
+        };
+
+        writeln!(file, "{}", entry.to_line())?;
+    }
+
+    Ok(())
+}
+
+/// Reads the history, invalid lines are skipped.
+pub fn load(path: &Path) -> io::Result<Vec<Entry>> {
+    BufReader::new(File::open(path)?)
+        .lines()
+        .filter_map(|line| line.map(|line| Entry::from_line(&line)).transpose())
+        .collect()
+}
+
+fn median(durations: &[Duration]) -> Duration {
+    let mut durations = durations.to_vec();
+
+    durations.sort_unstable();
+    durations[durations.len() / 2]
+}
+
+fn sparkline(durations: &[Duration]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let (min, max) = (durations.iter().min(), durations.iter().max());
+    let (Some(&min), Some(&max)) = (min, max) else {
+        return String::new();
+    };
+    let range = (max - min).as_secs_f64();
+
+    durations
+        .iter()
+        .map(|&d| {
+            if range == 0.0 {
+                BARS[0]
+            } else {
+                BARS[((d - min).as_secs_f64() / range * 7.0).round() as usize]
+            }
+        })
+        .collect()
+}
+
+/// Latest timing of a day compared with the median of the `window`
+/// runs before it on the same host.
+#[derive(Debug, PartialEq)]
+pub struct Trend {
+    pub day_number: u32,
+    pub runs: Vec<Duration>,
+    pub last: Duration,
+    pub median: Option<Duration>,
+    /// Relative change from the median, 0.1 being 10% slower.
+    pub change: Option<f64>,
+}
+
+pub fn trends(entries: &[Entry], day_number: u32, window: usize) -> Option<Trend> {
+    let last = entries.iter().rfind(|e| e.day_number == day_number)?;
+    let runs: Vec<Duration> = entries
+        .iter()
+        .filter(|e| e.day_number == day_number && e.host == last.host)
+        .map(|e| e.duration)
+        .collect();
+    let previous = &runs[runs.len().saturating_sub(window + 1)..runs.len() - 1];
+    let median = (!previous.is_empty()).then(|| median(previous));
+    let change = median.map(|m| last.duration.as_secs_f64() / m.as_secs_f64() - 1.0);
+
+    Some(Trend {
+        day_number,
+        last: last.duration,
+        runs,
+        median,
+        change,
+    })
+}
+
+/// Prints the trend of every day found in the history. Returns false
+/// if a day got slower than its recent median by more than
+/// `threshold`.
+pub fn check(entries: &[Entry], days: &[u32], window: usize, threshold: f64) -> bool {
+    let mut all_days: Vec<u32> = entries.iter().map(|e| e.day_number).collect();
+
+    all_days.sort_unstable();
+    all_days.dedup();
+
+    let days = if days.is_empty() { &all_days } else { days };
+    let mut regressions = 0;
+
+    for trend in days.iter().filter_map(|&d| trends(entries, d, window)) {
+        let Trend {
+            day_number,
+            last,
+            change,
+            median,
+            ..
+        } = &trend;
+        let spark = sparkline(&trend.runs[trend.runs.len().saturating_sub(20)..]);
+        let mut line = format!(
+            "day{day_number:0>2}: {spark:20} {:3} runs last {last:?}",
+            trend.runs.len()
+        );
+
+        if let (Some(median), Some(change)) = (median, change) {
+            line += &format!(" median {median:?} {:+.1}%", change * 100.0);
+
+            if *change > threshold {
+                line += " REGRESSION";
+                regressions += 1;
+            }
+        }
+
+        println!("{line}");
+    }
+
+    if regressions > 0 {
+        println!("{regressions} days got slower than their recent median");
+    }
+
+    regressions == 0
+}
+
+#[cfg(test)]
+fn entries_test(durations: &[(u32, u64)]) -> Vec<Entry> {
+    durations
+        .iter()
+        .enumerate()
+        .map(|(i, &(day_number, ms))| Entry {
+            timestamp: i as u64,
+            revision: "abc".to_string(),
+            host: "host".to_string(),
+            day_number,
+            duration: Duration::from_millis(ms),
+            peak: None,
+        })
+        .collect()
+}
+
+#[test]
+fn check_entry_line() {
+    let entry = &entries_test(&[(3, 12)])[0];
+
+    assert_eq!(entry.to_line(), "0\tabc\thost\t3\t12000000\t-");
+    assert_eq!(Entry::from_line(&entry.to_line()).as_ref(), Some(entry));
+    assert_eq!(
+        Entry::from_line("0\tabc\thost\t3\t12000000").as_ref(),
+        Some(entry)
+    );
+    assert_eq!(
+        Entry::from_line("0\tabc\thost\t3\t12000000\t512").and_then(|e| e.peak),
+        Some(512)
+    );
+    assert_eq!(Entry::from_line("0\tabc\thost\t3"), None);
+}
+
+#[test]
+fn check_trends() {
+    let entries = entries_test(&[(1, 10), (2, 5), (1, 12), (1, 11), (2, 5), (1, 14)]);
+    let trend = trends(&entries, 1, 3).unwrap();
+
+    assert_eq!(trend.last, Duration::from_millis(14));
+    assert_eq!(trend.median, Some(Duration::from_millis(11)));
+    assert!(check(&entries, &[2], 3, 0.1));
+    assert!(!check(&entries, &[], 3, 0.1));
+    assert!(check(&entries, &[], 3, 0.5));
+    assert_eq!(trends(&entries, 3, 3), None);
+    assert_eq!(
+        trends(&entries_test(&[(1, 10)]), 1, 3).unwrap().median,
+        None
+    );
+}
@@ -0,0 +1,198 @@
+use crate::history::{self, Entry};
+use crate::memory::format_bytes;
+use crate::report::DayReport;
+use std::fmt::Write;
+use std::time::Duration;
+
+const BAR_WIDTH: f64 = 480.0;
+const BAR_HEIGHT: usize = 18;
+const SPARK_WIDTH: f64 = 160.0;
+const SPARK_RUNS: usize = 30;
+
+const STYLE: &str = "body { font-family: sans-serif; margin: 2em; color: #222; }
+table { border-collapse: collapse; }
+th, td { padding: 4px 10px; text-align: left; border-bottom: 1px solid #ddd; }
+td.num { text-align: right; font-family: monospace; }
+.legend span { display: inline-block; margin-right: 1em; }
+.legend i { display: inline-block; width: 12px; height: 12px; margin-right: 4px; }";
+
+fn phase_color(phase: &str) -> &'static str {
+    match phase {
+        "parse" => "#8da0cb",
+        "part1" => "#66c2a5",
+        "part2" => "#fc8d62",
+        _ => "#b3b3b3",
+    }
+}
+
+pub(crate) fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Horizontal bar of a day, split in its phases, scaled on the slowest
+/// day.
+fn timing_bar(report: &DayReport, slowest: Duration) -> String {
+    let scale = BAR_WIDTH / slowest.as_secs_f64().max(f64::MIN_POSITIVE);
+    let phases = if report.phases.is_empty() {
+        vec![("solve", report.duration)]
+    } else {
+        report.phases.clone()
+    };
+    let mut svg = format!(
+        "<svg width=\"{}\" height=\"{BAR_HEIGHT}\">",
+        BAR_WIDTH as usize
+    );
+    let mut x = 0.0;
+
+    for (phase, duration) in phases {
+        let width = duration.as_secs_f64() * scale;
+
+        let _ = write!(
+            svg,
+            "<rect x=\"{x:.1}\" width=\"{width:.1}\" height=\"{BAR_HEIGHT}\" fill=\"{}\"><title>{phase} {duration:.2?}</title></rect>",
+            phase_color(phase)
+        );
+        x += width;
+    }
+
+    svg + "</svg>"
+}
+
+fn memory_bar(peak: usize, largest: usize) -> String {
+    let width = BAR_WIDTH * peak as f64 / largest.max(1) as f64;
+
+    format!(
+        "<svg width=\"{}\" height=\"{BAR_HEIGHT}\"><rect width=\"{width:.1}\" height=\"{BAR_HEIGHT}\" fill=\"#e78ac3\"><title>{}</title></rect></svg>",
+        BAR_WIDTH as usize,
+        format_bytes(peak)
+    )
+}
+
+/// Polyline of the recorded durations of a day, the slowest on top.
+fn sparkline(runs: &[Duration]) -> String {
+    let (Some(min), Some(max)) = (runs.iter().min(), runs.iter().max()) else {
+        return String::new();
+    };
+    let range = (*max - *min).as_secs_f64();
+    let step = SPARK_WIDTH / (runs.len().max(2) - 1) as f64;
+    let points: Vec<String> = runs
+        .iter()
+        .enumerate()
+        .map(|(i, &d)| {
+            let y = if range == 0.0 {
+                0.5
+            } else {
+                1.0 - (d - *min).as_secs_f64() / range
+            };
+
+            format!(
+                "{:.1},{:.1}",
+                i as f64 * step,
+                2.0 + y * (BAR_HEIGHT - 4) as f64
+            )
+        })
+        .collect();
+
+    format!(
+        "<svg width=\"{}\" height=\"{BAR_HEIGHT}\"><polyline points=\"{}\" fill=\"none\" stroke=\"#555\" stroke-width=\"1.5\"><title>{} runs from {min:.2?} to {max:.2?}</title></polyline></svg>",
+        SPARK_WIDTH as usize,
+        points.join(" "),
+        runs.len()
+    )
+}
+
+/// Self-contained HTML page of a run: the timing of each day split in
+/// its phases, the peak memory and the trend of the recorded timings.
+pub fn render(reports: &[DayReport], entries: &[Entry]) -> String {
+    let slowest = reports.iter().map(|r| r.duration).max().unwrap_or_default();
+    let largest = reports
+        .iter()
+        .filter_map(|r| r.memory.map(|m| m.peak))
+        .max();
+    let total: Duration = reports.iter().map(|r| r.duration).sum();
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Advent of Code 2025 performance</title>\n<style>\n{STYLE}\n</style>\n</head>\n<body>\n<h1>Advent of Code 2025 performance</h1>\n<p>{} days in {total:.2?}</p>\n<p class=\"legend\">",
+        reports.len()
+    );
+
+    // The days computing both parts at once are not split further.
+    for (phase, label) in [
+        ("parse", "parse"),
+        ("part1", "part1"),
+        ("part2", "part2"),
+        ("solve", "solve, both parts together"),
+    ] {
+        let _ = write!(
+            html,
+            "<span><i style=\"background: {}\"></i>{label}</span>",
+            phase_color(phase)
+        );
+    }
+
+    html += "</p>\n<table>\n<tr><th>Day</th><th>Part 1</th><th>Part 2</th><th>Time</th><th></th>";
+    if largest.is_some() {
+        html += "<th>Peak memory</th><th></th>";
+    }
+    html += "<th>History</th></tr>\n";
+
+    for report in reports {
+        let _ = write!(
+            html,
+            "<tr><td>{:0>2}</td><td>{}</td><td>{}</td><td class=\"num\">{:.2?}</td><td>{}</td>",
+            report.day_number,
+            escape(&report.part1),
+            escape(&report.part2),
+            report.duration,
+            timing_bar(report, slowest)
+        );
+        if let Some(largest) = largest {
+            let peak = report.memory.map_or(0, |m| m.peak);
+
+            let _ = write!(
+                html,
+                "<td class=\"num\">{}</td><td>{}</td>",
+                format_bytes(peak),
+                memory_bar(peak, largest)
+            );
+        }
+
+        let runs = history::trends(entries, report.day_number, 0)
+            .map(|trend| trend.runs[trend.runs.len().saturating_sub(SPARK_RUNS)..].to_vec())
+            .unwrap_or_default();
+
+        let _ = writeln!(html, "<td>{}</td></tr>", sparkline(&runs));
+    }
+
+    html + "</table>\n</body>\n</html>\n"
+}
+
+#[test]
+fn check_render() {
+    let report = DayReport {
+        day_number: 4,
+        part1: "<1>".to_string(),
+        part2: "2".to_string(),
+        duration: Duration::from_millis(4),
+        phases: vec![
+            ("parse", Duration::from_millis(1)),
+            ("part1", Duration::from_millis(3)),
+        ],
+        cpu: None,
+        memory: None,
+    };
+    let html = render(&[report], &[]);
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<td>&lt;1&gt;</td>"));
+    assert!(html.contains("<rect x=\"120.0\" width=\"360.0\""));
+    assert!(!html.contains("<script"));
+    assert!(!html.contains("Peak memory"));
+    assert_eq!(sparkline(&[]), "");
+    assert!(
+        sparkline(&[Duration::from_millis(2), Duration::from_millis(1)])
+            .contains("points=\"0.0,2.0 160.0,16.0\"")
+    );
+}
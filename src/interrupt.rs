@@ -0,0 +1,41 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Exit code of an interrupted run, as if killed by SIGINT.
+pub const EXIT_CODE: i32 = 130;
+
+static INTERRUPTS: AtomicUsize = AtomicUsize::new(0);
+
+#[cfg(unix)]
+extern "C" fn on_interrupt(_signal: libc::c_int) {
+    INTERRUPTS.fetch_add(1, Ordering::SeqCst);
+}
+
+/// Counts the Ctrl-C instead of terminating the process, from zero,
+/// until `restore`.
+pub fn install() {
+    INTERRUPTS.store(0, Ordering::SeqCst);
+
+    #[cfg(unix)]
+    // SAFETY: the handler only touches an atomic, which is async signal
+    // safe.
+    unsafe {
+        libc::signal(
+            libc::SIGINT,
+            on_interrupt as extern "C" fn(libc::c_int) as libc::sighandler_t,
+        );
+    }
+}
+
+/// Lets Ctrl-C terminate the process again.
+pub fn restore() {
+    #[cfg(unix)]
+    // SAFETY: SIG_DFL is the handler the process started with.
+    unsafe {
+        libc::signal(libc::SIGINT, libc::SIG_DFL);
+    }
+}
+
+/// Number of Ctrl-C received since `install`.
+pub fn count() -> usize {
+    INTERRUPTS.load(Ordering::SeqCst)
+}
@@ -1,95 +1,320 @@
+pub mod cpu;
+pub mod geom;
+pub mod graph;
+pub mod grid;
+pub mod history;
+pub mod html;
+pub mod interrupt;
+pub mod memory;
+pub mod parse;
+pub mod profile;
+pub mod progress;
+pub mod puzzle;
+pub mod random;
+pub mod rangeset;
+pub mod report;
+pub mod timing;
+pub mod trace;
+pub mod tui;
+pub mod verify;
+
+use cpu::CpuTime;
 use curl::easy::Easy;
+use memory::MemoryCounter;
+use random::Rng;
+use report::{DayReport, Format, Report};
+use std::any::Any;
 use std::cmp::{Eq, Ord, Ordering};
 use std::fs::File;
 use std::io::{self, BufRead, BufReader, Lines, Write};
-use std::path::Path;
-use std::time::Instant;
+use std::ops::Range;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime};
 
-pub struct Paragraph<'a, T, F, O>
-where
-    F: Fn(String) -> O,
-{
+#[cfg(feature = "count-allocations")]
+#[global_allocator]
+static ALLOCATOR: memory::CountingAllocator = memory::CountingAllocator;
+
+pub struct Paragraph<'a, T, F> {
     lines: &'a mut Lines<T>,
     transform: F,
+    whitespace_separators: bool,
 }
 
-pub trait Paragrapher<T, F, O>
-where
-    F: Fn(String) -> O,
-{
-    fn split_paragraph(&mut self, transfom: F) -> Paragraph<'_, T, F, O>;
+impl<T, F> Paragraph<'_, T, F> {
+    /// Lines containing only whitespaces separate paragraphs too.
+    pub fn whitespace_separators(mut self) -> Self {
+        self.whitespace_separators = true;
+        self
+    }
+}
+
+pub trait Paragrapher<T> {
+    fn split_paragraph<F, O>(
+        &mut self,
+        transform: F,
+    ) -> Paragraph<'_, T, impl FnMut(String) -> io::Result<O>>
+    where
+        F: FnMut(String) -> O;
+
+    fn try_split_paragraph<F, O, E>(&mut self, transform: F) -> Paragraph<'_, T, F>
+    where
+        F: FnMut(String) -> Result<O, E>,
+        E: From<io::Error>;
+
+    /// Splits an input made of two sections. Paragraphs accepted by
+    /// `in_first` are transformed by `first`, all the lines after
+    /// the first paragraph rejected are transformed by
+    /// `second`. Whitespace lines are separators.
+    fn two_sections<P, F, G, A, B, E>(
+        self,
+        in_first: P,
+        first: F,
+        second: G,
+    ) -> Result<(Vec<A>, Vec<B>), E>
+    where
+        P: FnMut(&[String]) -> bool,
+        F: FnMut(Vec<String>) -> Result<A, E>,
+        G: FnMut(String) -> Result<B, E>,
+        E: From<io::Error>;
 }
 
-impl<T, F, O> Paragrapher<T, F, O> for Lines<T>
+impl<T> Paragrapher<T> for Lines<T>
 where
-    F: Fn(String) -> O,
+    T: BufRead,
 {
-    fn split_paragraph(&mut self, transform: F) -> Paragraph<'_, T, F, O> {
+    fn split_paragraph<F, O>(
+        &mut self,
+        mut transform: F,
+    ) -> Paragraph<'_, T, impl FnMut(String) -> io::Result<O>>
+    where
+        F: FnMut(String) -> O,
+    {
+        self.try_split_paragraph(move |line| Ok(transform(line)))
+    }
+
+    fn try_split_paragraph<F, O, E>(&mut self, transform: F) -> Paragraph<'_, T, F>
+    where
+        F: FnMut(String) -> Result<O, E>,
+        E: From<io::Error>,
+    {
         Paragraph {
             lines: self,
             transform,
+            whitespace_separators: false,
         }
     }
+
+    fn two_sections<P, F, G, A, B, E>(
+        mut self,
+        mut in_first: P,
+        mut first: F,
+        mut second: G,
+    ) -> Result<(Vec<A>, Vec<B>), E>
+    where
+        P: FnMut(&[String]) -> bool,
+        F: FnMut(Vec<String>) -> Result<A, E>,
+        G: FnMut(String) -> Result<B, E>,
+        E: From<io::Error>,
+    {
+        let (mut a, mut b) = (vec![], vec![]);
+        let paragraphs = self
+            .try_split_paragraph(Ok::<String, E>)
+            .whitespace_separators();
+        let mut is_first = true;
+
+        for p in paragraphs {
+            let p = p?;
+
+            is_first = is_first && in_first(&p);
+
+            if is_first {
+                a.push(first(p)?);
+            } else {
+                for line in p {
+                    b.push(second(line)?);
+                }
+            }
+        }
+
+        Ok((a, b))
+    }
 }
 
-impl<T, F, O> Iterator for Paragraph<'_, T, F, O>
+impl<T, F, O, E> Iterator for Paragraph<'_, T, F>
 where
     T: BufRead,
-    F: Fn(String) -> O,
+    F: FnMut(String) -> Result<O, E>,
+    E: From<io::Error>,
 {
-    type Item = Vec<O>;
+    type Item = Result<Vec<O>, E>;
 
     fn next(&mut self) -> Option<Self::Item> {
         let mut p = vec![];
 
         for line in self.lines.by_ref() {
-            let line = line.unwrap();
+            let line = match line {
+                Ok(line) => line,
+                Err(error) => return Some(Err(error.into())),
+            };
+            let is_separator = if self.whitespace_separators {
+                line.trim().is_empty()
+            } else {
+                line.is_empty()
+            };
 
-            if line.is_empty() {
+            if is_separator {
+                if p.is_empty() {
+                    // Several separators in a row.
+                    continue;
+                }
                 break;
             }
 
-            p.push((self.transform)(line));
+            match (self.transform)(line) {
+                Ok(o) => p.push(o),
+                Err(error) => return Some(Err(error)),
+            }
         }
 
         if p.is_empty() {
             None
         } else {
-            Some(p)
+            Some(Ok(p))
         }
     }
 }
 
+#[cfg(test)]
+const PARAGRAPHS_TEST: &str = "1
+2
+
+ 	
+3
+
+a
+4";
+
+#[test]
+fn check_split_paragraph() {
+    use std::io::Cursor;
+
+    let mut lines = Cursor::new(PARAGRAPHS_TEST).lines();
+    let paragraphs: Vec<Vec<usize>> = lines
+        .split_paragraph(|line| line.len())
+        .collect::<io::Result<_>>()
+        .unwrap();
+
+    assert_eq!(paragraphs, [vec![1, 1], vec![2, 1], vec![1, 1]]);
+
+    let mut lines = Cursor::new(PARAGRAPHS_TEST).lines();
+    let paragraphs: Vec<Vec<String>> = lines
+        .split_paragraph(|line| line)
+        .whitespace_separators()
+        .collect::<io::Result<_>>()
+        .unwrap();
+
+    assert_eq!(paragraphs, [vec!["1", "2"], vec!["3"], vec!["a", "4"]]);
+}
+
+#[test]
+fn check_try_split_paragraph() {
+    use std::error::Error;
+    use std::io::Cursor;
+
+    let mut lines = Cursor::new(PARAGRAPHS_TEST).lines();
+    let mut paragraphs = lines
+        .try_split_paragraph(|line| line.trim().parse::<u32>().map_err(Box::<dyn Error>::from));
+
+    assert_eq!(paragraphs.next().unwrap().unwrap(), [1, 2]);
+    assert!(paragraphs.next().unwrap().is_err());
+}
+
+#[test]
+fn check_two_sections() {
+    use std::io::Cursor;
+
+    let (first, second) = Cursor::new(PARAGRAPHS_TEST)
+        .lines()
+        .two_sections(|p| p[0] != "a", |p| Ok::<_, io::Error>(p.len()), Ok)
+        .unwrap();
+
+    assert_eq!(first, [2, 1]);
+    assert_eq!(second, ["a", "4"]);
+}
+
+/// Lines of a day input, read from a file or from memory.
+pub type Input = Lines<Box<dyn BufRead>>;
+
+fn input_from_string(input: String) -> Input {
+    (Box::new(io::Cursor::new(input)) as Box<dyn BufRead>).lines()
+}
+
 #[derive(Eq)]
 pub struct Day {
     day_filename: &'static str,
-    resolve: fn(Lines<BufReader<File>>) -> (String, String),
+    resolve: fn(Input) -> (String, String),
 }
 
 impl Day {
-    pub const fn new(
-        day_filename: &'static str,
-        resolve: fn(Lines<BufReader<File>>) -> (String, String),
-    ) -> Self {
+    pub const fn new(day_filename: &'static str, resolve: fn(Input) -> (String, String)) -> Self {
         Day {
             day_filename,
             resolve,
         }
     }
 
-    fn print(&self, session: Option<&str>) {
+    fn run(&self, session: Option<&str>) -> DayReport {
+        let memory = MemoryCounter::start();
+        let cpu = CpuTime::now();
+
+        trace::enter(self.parse_number());
+
+        // Cleared when dropped, even if the day panics.
+        let _progress = progress::Display::start(self.parse_number());
         let start = Instant::now();
+        // From the same instant, the phases add up to the duration.
+        let laps = timing::Recorder::start(start);
         let (day_number, part1, part2) = self.resolve(session);
-        let duration = start.elapsed();
+        let end = Instant::now();
+
+        trace::leave();
+
+        let phases = laps.stop(end);
 
-        println!("day{day_number:0>2}: part1: {part1:20} part2: {part2:20} in {duration:?}");
+        if profile::recording() {
+            let mut phase_start = start;
+
+            for &(phase, duration) in phases.iter() {
+                profile::record(
+                    phase.to_string(),
+                    "phase",
+                    phase_start,
+                    phase_start + duration,
+                );
+                phase_start += duration;
+            }
+            profile::record(format!("day{day_number:0>2}"), "day", start, end);
+        }
+
+        let cpu = cpu.zip(CpuTime::now()).map(|(start, end)| end - start);
+
+        DayReport {
+            day_number,
+            part1,
+            part2,
+            duration: end - start,
+            phases,
+            cpu,
+            memory: memory.map(MemoryCounter::stop),
+        }
     }
 
     fn parse_number(&self) -> u32 {
-        self.day_filename
-            .replace(|c: char| !c.is_ascii_digit(), "")
-            .parse::<u32>()
-            .unwrap()
+        parse_day_number(self.day_filename)
     }
 
     fn resolve(&self, session: Option<&str>) -> (u32, String, String) {
@@ -117,76 +342,1065 @@ impl PartialOrd for Day {
     }
 }
 
-fn read_lines(
-    session: Option<&str>,
-    day_number: u32,
-) -> io::Result<io::Lines<io::BufReader<File>>> {
-    let filename = format!("./inputs/{day_number:0>2}.txt");
-    let path = Path::new(&filename);
+fn parse_day_number(day_filename: &str) -> u32 {
+    day_filename
+        .replace(|c: char| !c.is_ascii_digit(), "")
+        .parse::<u32>()
+        .unwrap()
+}
 
-    if !path.exists() {
-        let session = session
-            .expect("set AOC session id using command line or AOC_SESSION environment variable");
+/// Another implementation of a day, registered next to its `Day`, that
+/// must give the same answers.
+pub struct Variant {
+    day_filename: &'static str,
+    name: &'static str,
+    resolve: fn(Input) -> (String, String),
+}
 
-        println!("downloading input for day {day_number}");
+impl Variant {
+    pub const fn new(
+        day_filename: &'static str,
+        name: &'static str,
+        resolve: fn(Input) -> (String, String),
+    ) -> Self {
+        Variant {
+            day_filename,
+            name,
+            resolve,
+        }
+    }
+
+    fn parse_number(&self) -> u32 {
+        parse_day_number(self.day_filename)
+    }
+}
+
+/// Random input generator of a day. The meaning of `scale` depends on
+/// the day (number of lines, grid size...).
+pub struct Generator {
+    day_filename: &'static str,
+    generate: fn(&mut Rng, usize) -> String,
+}
+
+impl Generator {
+    pub const fn new(day_filename: &'static str, generate: fn(&mut Rng, usize) -> String) -> Self {
+        Generator {
+            day_filename,
+            generate,
+        }
+    }
 
-        // Try to create inputs directory
-        match std::fs::create_dir("./inputs") {
-            Ok(()) => {}
-            Err(error) => match error.kind() {
-                io::ErrorKind::AlreadyExists => {}
-                _ => panic!("unable to create ./inputs/ directory: {error}"),
-            },
+    fn parse_number(&self) -> u32 {
+        parse_day_number(self.day_filename)
+    }
+}
+
+/// How the input of a day is cut into sections of items while
+/// shrinking it, for the days whose lines are not independent. The
+/// days without one are cut into paragraphs of lines, like
+/// `two_sections` does.
+pub struct Sections {
+    day_filename: &'static str,
+    split: fn(&str) -> Vec<Vec<String>>,
+    join: fn(&[Vec<String>]) -> String,
+}
+
+impl Sections {
+    pub const fn new(
+        day_filename: &'static str,
+        split: fn(&str) -> Vec<Vec<String>>,
+        join: fn(&[Vec<String>]) -> String,
+    ) -> Self {
+        Sections {
+            day_filename,
+            split,
+            join,
         }
+    }
+
+    fn parse_number(&self) -> u32 {
+        parse_day_number(self.day_filename)
+    }
+}
+
+const PARAGRAPHS: Sections = Sections::new("", split_paragraphs, join_paragraphs);
 
-        let mut file = File::create(path)?;
-        let mut handle = Easy::new();
+fn find_sections(day_number: u32) -> &'static Sections {
+    inventory::iter::<Sections>
+        .into_iter()
+        .find(|s| s.parse_number() == day_number)
+        .unwrap_or(&PARAGRAPHS)
+}
 
-        handle.cookie(&format!("session={session}"))?;
-        handle.url(&format!(
-            "https://adventofcode.com/2025/day/{day_number}/input"
-        ))?;
+fn find_generator(day_number: u32) -> &'static Generator {
+    inventory::iter::<Generator>
+        .into_iter()
+        .find(|g| g.parse_number() == day_number)
+        .expect("no generator for this day")
+}
+
+/// Generates an input for a day, the same seed and scale always
+/// giving the same input.
+pub fn generate(day_number: u32, seed: u64, scale: usize) -> String {
+    (find_generator(day_number).generate)(&mut Rng::new(seed), scale)
+}
+
+/// Generates an input and solves it instead of printing it.
+pub fn generate_and_resolve(day_number: u32, seed: u64, scale: usize) {
+    let input = generate(day_number, seed, scale);
+    let lines = input.lines().count();
+    let day = select_days(&[day_number])[0];
+    let start = Instant::now();
+    let (part1, part2) = (day.resolve)(input_from_string(input));
+    let duration = start.elapsed();
+
+    println!(
+        "day{day_number:0>2}: seed {seed} scale {scale} ({lines} lines): part1: {part1:20} part2: {part2:20} in {duration:?}"
+    );
+}
+
+/// Downloads a page of the Advent Of Code site to `path` in the
+/// inputs directory.
+fn download(session: Option<&str>, url: &str, path: &Path) -> io::Result<Vec<u8>> {
+    let session =
+        session.expect("set AOC session id using command line or AOC_SESSION environment variable");
+
+    // Try to create inputs directory
+    match std::fs::create_dir("./inputs") {
+        Ok(()) => {}
+        Err(error) => match error.kind() {
+            io::ErrorKind::AlreadyExists => {}
+            _ => panic!("unable to create ./inputs/ directory: {error}"),
+        },
+    }
 
-        handle.write_function(move |data| {
-            file.write_all(data).unwrap();
-            Ok(data.len())
+    let mut data = vec![];
+    let mut handle = Easy::new();
+
+    handle.cookie(&format!("session={session}"))?;
+    handle.url(url)?;
+
+    {
+        let mut transfer = handle.transfer();
+
+        transfer.write_function(|chunk| {
+            data.extend_from_slice(chunk);
+            Ok(chunk.len())
         })?;
-        handle.perform()?;
+        transfer.perform()?;
+    }
+
+    // Error pages, like the one of an expired session, are not cached.
+    match handle.response_code()? {
+        200 => {}
+        code => {
+            return Err(io::Error::other(format!(
+                "unable to download {url}: HTTP status {code}"
+            )))
+        }
+    }
+
+    File::create(path)?.write_all(&data)?;
+
+    Ok(data)
+}
+
+/// Set by the dashboard, which has its own status line.
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// Tells about a download, unless quiet.
+fn announce(message: std::fmt::Arguments) {
+    if !QUIET.load(atomic::Ordering::Relaxed) {
+        println!("{message}");
+    }
+}
+
+/// Where the input of a day is cached.
+fn input_path(day_number: u32) -> PathBuf {
+    PathBuf::from(format!("./inputs/{day_number:0>2}.txt"))
+}
+
+fn read_lines(session: Option<&str>, day_number: u32) -> io::Result<Input> {
+    let path = input_path(day_number);
+
+    if !path.exists() {
+        announce(format_args!("downloading input for day {day_number}"));
+
+        download(
+            session,
+            &format!("https://adventofcode.com/2025/day/{day_number}/input"),
+            &path,
+        )?;
     }
 
     let file = File::open(path)?;
 
-    Ok(io::BufReader::new(file).lines())
+    Ok((Box::new(BufReader::new(file)) as Box<dyn BufRead>).lines())
 }
 
-fn resolve_all(session: Option<&str>) {
-    let mut days: Vec<&'static Day> = inventory::iter::<Day>.into_iter().collect();
+/// The puzzle page of a day converted to Markdown, cached next to the
+/// input. Without a session, only the cached page can be used. With
+/// one, a cached page with less than two answers is downloaded again
+/// to catch the stars earned since.
+pub fn read_puzzle_markdown(session: Option<&str>, day_number: u32) -> io::Result<String> {
+    let filename = format!("./inputs/{day_number:0>2}.md");
+    let path = Path::new(&filename);
+    let cached = std::fs::read_to_string(path).ok();
+
+    if let Some(markdown) = &cached {
+        if session.is_none() || puzzle::Puzzle::from_markdown(markdown).stars() >= 2 {
+            return Ok(markdown.clone());
+        }
+    } else if session.is_none() {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{filename} is missing and no session is set"),
+        ));
+    }
+
+    announce(format_args!("downloading puzzle for day {day_number}"));
 
-    days.sort_unstable();
+    // The raw page is kept too, in case the conversion needs to be
+    // improved.
+    let html = match download(
+        session,
+        &format!("https://adventofcode.com/2025/day/{day_number}"),
+        &path.with_extension("html"),
+    ) {
+        Ok(html) => html,
+        Err(e) => match cached {
+            Some(markdown) => {
+                announce(format_args!("keeping the cached {filename}: {e}"));
+                return Ok(markdown);
+            }
+            None => return Err(e),
+        },
+    };
+    let markdown = puzzle::html_to_markdown(&String::from_utf8_lossy(&html));
+
+    std::fs::write(path, &markdown)?;
 
-    days.iter().for_each(|d| d.print(session));
+    Ok(markdown)
 }
 
-fn resolve_one(session: Option<&str>, day_number: u32) {
-    inventory::iter::<Day>
-        .into_iter()
-        .find(|d| d.parse_number() == day_number)
-        .expect("unable to find this day")
-        .print(session);
+/// Prints the summary of a run and records its timings, unless it was
+/// interrupted.
+fn finish_run(report: &Report, format: Format) {
+    match format {
+        Format::Text if report.interrupted => println!(
+            "Interrupted after {} days in {:?}",
+            report.days.len(),
+            report.duration
+        ),
+        Format::Text => println!("All done in {:?}", report.duration),
+        Format::Json => println!("{}", report.json()),
+    }
+
+    // Partial runs would skew the trends.
+    if report.interrupted {
+        return;
+    }
+
+    if let Err(e) = history::record(Path::new(history::HISTORY_FILE), report) {
+        eprintln!(
+            "unable to record the timings in {}: {e}",
+            history::HISTORY_FILE
+        );
+    }
 }
 
-pub fn resolve(session: Option<&str>, days: &[u32]) {
+/// Solves the days. A first Ctrl-C stops the run after the current
+/// day, a second one abandons it and exits with
+/// [`interrupt::EXIT_CODE`]. Either way the completed days are
+/// reported. Returns false if the run was interrupted.
+pub fn resolve(session: Option<&str>, days: &[u32], format: Format) -> bool {
     let start = Instant::now();
+    // The completed days, and whether the run is over.
+    let completed: Arc<Mutex<(Vec<DayReport>, bool)>> = Arc::default();
+
+    interrupt::install();
+
+    let watched = Arc::clone(&completed);
+
+    let watcher = std::thread::spawn(move || {
+        let mut warned = false;
+
+        loop {
+            std::thread::park_timeout(Duration::from_millis(50));
+
+            if watched.lock().unwrap().1 {
+                return;
+            }
+
+            match interrupt::count() {
+                0 => {}
+                1 if !warned => {
+                    eprintln!(
+                        "interrupted, stopping after the current day, Ctrl-C again to stop now"
+                    );
+                    warned = true;
+                }
+                1 => {}
+                _ => {
+                    let (days, over) = &*watched.lock().unwrap();
+
+                    // Too late, the summary is already printed.
+                    if *over {
+                        return;
+                    }
+
+                    let report = Report {
+                        days: days.clone(),
+                        duration: start.elapsed(),
+                        interrupted: true,
+                    };
+
+                    finish_run(&report, format);
+                    profile::save();
+                    std::process::exit(interrupt::EXIT_CODE);
+                }
+            }
+        }
+    });
+
+    let mut interrupted = false;
+
+    for day in select_days(days) {
+        if interrupt::count() > 0 {
+            interrupted = true;
+            break;
+        }
+
+        let report = day.run(session);
+
+        if format == Format::Text {
+            println!("{}", report.text());
+        }
+        completed.lock().unwrap().0.push(report);
+    }
+
+    // Ctrl-C during the last day.
+    interrupted = interrupted || interrupt::count() > 0;
+
+    let report = {
+        let mut completed = completed.lock().unwrap();
+
+        completed.1 = true;
+        Report {
+            days: std::mem::take(&mut completed.0),
+            duration: start.elapsed(),
+            interrupted,
+        }
+    };
+
+    watcher.thread().unpark();
+    let _ = watcher.join();
+    interrupt::restore();
+
+    finish_run(&report, format);
+    !interrupted
+}
+
+fn select_days(days: &[u32]) -> Vec<&'static Day> {
+    let mut all: Vec<&'static Day> = inventory::iter::<Day>.into_iter().collect();
+
+    all.sort_unstable();
 
     if days.is_empty() {
-        resolve_all(session);
+        all
     } else {
-        days.iter().for_each(|&d| resolve_one(session, d));
+        days.iter()
+            .map(|&d| {
+                *all.iter()
+                    .find(|day| day.parse_number() == d)
+                    .expect("unable to find this day")
+            })
+            .collect()
     }
+}
 
+fn timed_resolve(
+    resolve: fn(Input) -> (String, String),
+    session: Option<&str>,
+    day_number: u32,
+) -> (String, String, Duration) {
+    let lines = read_lines(session, day_number).unwrap();
+
+    trace::enter(day_number);
+
+    let start = Instant::now();
+    let (part1, part2) = resolve(lines);
     let duration = start.elapsed();
 
-    println!("All done in {duration:?}");
+    trace::leave();
+    (part1, part2, duration)
+}
+
+/// Runs every variant of the days and compares their answers with
+/// the main implementation. Returns false on any disagreement.
+pub fn compare(session: Option<&str>, days: &[u32]) -> bool {
+    let mut disagreements = vec![];
+
+    for day in select_days(days) {
+        let day_number = day.parse_number();
+        let (part1, part2, duration) = timed_resolve(day.resolve, session, day_number);
+
+        println!(
+            "day{day_number:0>2}: {:12} part1: {part1:20} part2: {part2:20} in {duration:?}",
+            "main"
+        );
+
+        for variant in inventory::iter::<Variant>
+            .into_iter()
+            .filter(|v| v.parse_number() == day_number)
+        {
+            let (p1, p2, d) = timed_resolve(variant.resolve, session, day_number);
+            let ratio = d.as_secs_f64() / duration.as_secs_f64();
+            let status = if (&p1, &p2) == (&part1, &part2) {
+                ""
+            } else {
+                disagreements.push((day_number, variant.name));
+                " DISAGREE"
+            };
+
+            println!(
+                "day{day_number:0>2}: {:12} part1: {p1:20} part2: {p2:20} in {d:?} (x{ratio:.2}){status}",
+                variant.name
+            );
+        }
+    }
+
+    for (day_number, name) in disagreements.iter() {
+        println!("day{day_number:0>2}: variant {name} disagrees with the main implementation");
+    }
+
+    disagreements.is_empty()
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+/// Failures are reported by the caller, no need for the default panic
+/// message and backtrace.
+fn without_panic_messages<R>(f: impl FnOnce() -> R) -> R {
+    let hook = panic::take_hook();
+
+    panic::set_hook(Box::new(|_| {}));
+
+    // Caught to restore the hook before going on, as it can't be set
+    // while panicking.
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(f));
+
+    panic::set_hook(hook);
+    result.unwrap_or_else(|payload| panic::resume_unwind(payload))
+}
+
+/// Answers of a solver, or the message of its panic.
+fn catch_resolve(
+    resolve: fn(Input) -> (String, String),
+    input: &str,
+) -> Result<(String, String), String> {
+    panic::catch_unwind(|| resolve(input_from_string(input.to_string())))
+        .map_err(|payload| panic_message(&*payload))
+}
+
+/// Solves an input with the main implementation and every variant of
+/// the day, returns a description of the first problem found.
+fn check_input(day: &Day, input: &str) -> Option<String> {
+    let day_number = day.parse_number();
+    let (part1, part2) = match catch_resolve(day.resolve, input) {
+        Ok(answers) => answers,
+        Err(message) => return Some(format!("main panicked: {message}")),
+    };
+
+    inventory::iter::<Variant>
+        .into_iter()
+        .filter(|v| v.parse_number() == day_number)
+        .find_map(|variant| match catch_resolve(variant.resolve, input) {
+            Ok((p1, p2)) if (&p1, &p2) == (&part1, &part2) => None,
+            Ok((p1, p2)) => Some(format!(
+                "{} disagrees: part1: {p1} part2: {p2} instead of part1: {part1} part2: {part2}",
+                variant.name
+            )),
+            Err(message) => Some(format!("{} panicked: {message}", variant.name)),
+        })
+}
+
+/// Seeds of the generated inputs making a day panic or disagree with
+/// its variants, with the problem. The panic messages are not hidden.
+pub fn fuzz_failures(day_number: u32, seeds: Range<u64>, scale: usize) -> Vec<(u64, String)> {
+    let day = select_days(&[day_number])[0];
+
+    seeds
+        .filter_map(|seed| {
+            check_input(day, &generate(day_number, seed, scale)).map(|problem| (seed, problem))
+        })
+        .collect()
+}
+
+/// Solves generated inputs with the main implementation and every
+/// variant of the days, printing the seeds giving a panic or a
+/// disagreement. Returns false if there is any.
+pub fn fuzz(days: &[u32], seeds: Range<u64>, scale: usize) -> bool {
+    let mut success = true;
+
+    for day in select_days(days) {
+        let day_number = day.parse_number();
+        let start = Instant::now();
+        let failures = without_panic_messages(|| fuzz_failures(day_number, seeds.clone(), scale));
+
+        for (seed, problem) in &failures {
+            println!("day{day_number:0>2}: seed {seed}: {problem}");
+        }
+
+        let duration = start.elapsed();
+        let count = seeds.end - seeds.start;
+
+        match failures.first() {
+            None => println!("day{day_number:0>2}: {count} seeds ok in {duration:?}"),
+            Some((seed, _)) => {
+                println!(
+                    "day{day_number:0>2}: {} of {count} seeds failed, reproduce with: gen {day_number} --seed {seed} --scale {scale}",
+                    failures.len()
+                );
+                success = false;
+            }
+        }
+    }
+
+    success
+}
+
+/// Removes chunks of `items`, then smaller and smaller ones down to
+/// single items, as long as `fails` holds. `items` must fail.
+fn minimize<T, F>(mut items: Vec<T>, mut fails: F) -> Vec<T>
+where
+    T: Clone,
+    F: FnMut(&[T]) -> bool,
+{
+    let mut size = items.len().div_ceil(2);
+
+    while size > 0 {
+        let mut start = 0;
+
+        while start < items.len() {
+            let end = (start + size).min(items.len());
+            let candidate = [&items[..start], &items[end..]].concat();
+
+            if !candidate.is_empty() && fails(&candidate) {
+                items = candidate;
+            } else {
+                start = end;
+            }
+        }
+
+        size /= 2;
+    }
+
+    items
+}
+
+#[test]
+fn check_minimize() {
+    let items: Vec<u32> = (0..100).collect();
+
+    assert_eq!(
+        minimize(items, |v| v.contains(&17) && v.contains(&42)),
+        [17, 42]
+    );
+    assert_eq!(minimize(vec![1, 2, 3], |v| v.len() >= 2), [1, 2]);
+}
+
+fn split_paragraphs(input: &str) -> Vec<Vec<String>> {
+    input_from_string(input.to_string())
+        .split_paragraph(|line| line)
+        .whitespace_separators()
+        .collect::<io::Result<_>>()
+        .unwrap()
+}
+
+fn join_paragraphs(paragraphs: &[Vec<String>]) -> String {
+    paragraphs
+        .iter()
+        .map(|p| p.iter().map(|line| format!("{line}\n")).collect::<String>())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Deletes sections, then items of the remaining sections, while the
+/// day keeps failing the same way. Returns the minimal input and its
+/// failure, or None if the input doesn't fail.
+fn shrink_input(day_number: u32, input: &str) -> Option<(String, String)> {
+    let day = select_days(&[day_number])[0];
+    let Sections { split, join, .. } = find_sections(day_number);
+    let problem = without_panic_messages(|| check_input(day, input))?;
+    // Only the failing solver has to match, answers and panic
+    // messages change while shrinking.
+    let kind = problem.split(':').next().unwrap().to_string();
+    let fails = |input: &str| {
+        without_panic_messages(|| check_input(day, input))
+            .is_some_and(|p| p.split(':').next() == Some(&kind))
+    };
+    let mut sections = split(input);
+    let mut previous = input.to_string();
+
+    println!("day{day_number:0>2}: {problem}");
+
+    loop {
+        sections = minimize(sections, |s| fails(&join(s)));
+
+        for i in 0..sections.len() {
+            let items = std::mem::take(&mut sections[i]);
+
+            sections[i] = minimize(items, |items| {
+                let mut candidate = sections.clone();
+
+                candidate[i] = items.to_vec();
+                fails(&join(&candidate))
+            });
+        }
+
+        let input = join(&sections);
+
+        println!(
+            "day{day_number:0>2}: {} bytes -> {} bytes",
+            previous.len(),
+            input.len()
+        );
+
+        // Deleting an item may allow to delete more sections.
+        if input == previous {
+            let problem = without_panic_messages(|| check_input(day, &input)).unwrap();
+
+            return Some((input, problem));
+        }
+        previous = input;
+    }
+}
+
+/// A test checking the answers of the reference implementation, or
+/// just calling the main one if it panics.
+fn regression_test(day_number: u32, input: &str) -> String {
+    let day = select_days(&[day_number])[0];
+    // Verbatim, the trailing spaces may matter.
+    let escaped = input.replace('\\', "\\\\").replace('"', "\\\"");
+    let reference = inventory::iter::<Variant>
+        .into_iter()
+        .filter(|v| v.parse_number() == day_number)
+        .find_map(|v| without_panic_messages(|| catch_resolve(v.resolve, input)).ok());
+    let answers = without_panic_messages(|| catch_resolve(day.resolve, input));
+    let check = match (answers, reference) {
+        (Ok(answers), Some(expected)) if answers != expected => format!(
+            "assert_eq!(resolve(Cursor::new(TEST).lines()), ({}, {}));",
+            expected.0, expected.1
+        ),
+        _ => "resolve(Cursor::new(TEST).lines());".to_string(),
+    };
+
+    format!(
+        "#[test]\nfn check_shrunk() {{\n    const TEST: &str = \"{escaped}\";\n    use std::io::Cursor;\n\n    {check}\n}}\n"
+    )
+}
+
+/// Shrinks a failing input and writes a regression test made of it to
+/// `output`. Returns false if the input doesn't fail.
+pub fn shrink(day_number: u32, input: &str, output: &Path) -> io::Result<bool> {
+    let Some((input, problem)) = shrink_input(day_number, input) else {
+        println!("day{day_number:0>2}: no failure to shrink");
+        return Ok(false);
+    };
+
+    std::fs::write(output, regression_test(day_number, &input))?;
+
+    println!("day{day_number:0>2}: {problem}");
+    println!(
+        "day{day_number:0>2}: reproducer written to {}",
+        output.display()
+    );
+
+    Ok(true)
+}
+
+/// The whole input of a day, downloaded if needed.
+pub fn read_input(session: Option<&str>, day_number: u32) -> io::Result<String> {
+    read_lines(session, day_number)?
+        .map(|line| line.map(|line| line + "\n"))
+        .collect()
+}
+
+/// Median time of `runs` resolutions of an input.
+fn median_duration(resolve: fn(Input) -> (String, String), input: &str, runs: usize) -> Duration {
+    let mut durations: Vec<Duration> = (0..runs.max(1))
+        .map(|_| {
+            let lines = input_from_string(input.to_string());
+            let start = Instant::now();
+
+            resolve(lines);
+            start.elapsed()
+        })
+        .collect();
+
+    durations.sort_unstable();
+    durations[durations.len() / 2]
+}
+
+/// 1, 2, 4... up to `max`, `max` included.
+fn thread_counts(max: usize) -> Vec<usize> {
+    let mut counts: Vec<usize> = std::iter::successors(Some(1usize), |n| n.checked_mul(2))
+        .take_while(|&n| n < max)
+        .collect();
+
+    counts.push(max.max(1));
+    counts
+}
+
+#[test]
+fn check_thread_counts() {
+    assert_eq!(thread_counts(1), [1]);
+    assert_eq!(thread_counts(8), [1, 2, 4, 8]);
+    assert_eq!(thread_counts(6), [1, 2, 4, 6]);
+}
+
+/// Prints the median time of every day. With `scaling`, the days run
+/// in pools of 1, 2, 4... up to `scaling` threads, and the speedups
+/// are compared to the single thread run.
+pub fn bench(session: Option<&str>, days: &[u32], runs: usize, scaling: Option<usize>) {
+    for day in select_days(days) {
+        let day_number = day.parse_number();
+        let input = read_input(session, day_number).unwrap();
+
+        let Some(max_threads) = scaling else {
+            let median = median_duration(day.resolve, &input, runs);
+
+            println!("day{day_number:0>2}: median {median:?} over {runs} runs");
+            continue;
+        };
+
+        let mut single = None;
+
+        for threads in thread_counts(max_threads) {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .unwrap();
+            let median = pool.install(|| median_duration(day.resolve, &input, runs));
+            let single = *single.get_or_insert(median);
+            let speedup = single.as_secs_f64() / median.as_secs_f64();
+
+            println!(
+                "day{day_number:0>2}: {threads:3} threads median {median:12?} speedup x{speedup:.2} efficiency {:3.0}%",
+                100.0 * speedup / threads as f64
+            );
+        }
+    }
+}
+
+/// Org table of every day, with the title and stars from the puzzle
+/// page. The timings come from a new run, or from the history.
+pub fn results_table(session: Option<&str>, recorded: bool) -> io::Result<String> {
+    let entries = if recorded {
+        history::load(Path::new(history::HISTORY_FILE))?
+    } else {
+        vec![]
+    };
+    let rows: Vec<Vec<String>> = select_days(&[])
+        .into_iter()
+        .map(|day| {
+            let day_number = day.parse_number();
+            let (duration, peak) = if recorded {
+                entries
+                    .iter()
+                    .rfind(|e| e.day_number == day_number)
+                    .map_or((None, None), |e| (Some(e.duration), e.peak))
+            } else {
+                let report = day.run(session);
+
+                (Some(report.duration), report.memory.map(|m| m.peak))
+            };
+            let puzzle = read_puzzle_markdown(session, day_number)
+                .map(|markdown| puzzle::Puzzle::from_markdown(&markdown))
+                .unwrap_or_default();
+
+            vec![
+                format!("{day_number:0>2}"),
+                puzzle.title.unwrap_or_else(|| "-".to_string()),
+                "★".repeat(puzzle.answers.len()),
+                duration.map_or("-".to_string(), |d| format!("{d:.2?}")),
+                peak.map_or("-".to_string(), memory::format_bytes),
+            ]
+        })
+        .collect();
+
+    Ok(report::org_table(
+        &["Day", "Title", "Stars", "Time", "Peak memory"],
+        &rows,
+    ))
+}
+
+/// Regenerates the results section of an Org file.
+pub fn update_readme(path: &Path, table: &str) -> io::Result<()> {
+    let text = match std::fs::read_to_string(path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        text => text?,
+    };
+
+    std::fs::write(path, report::replace_section(&text, table))
+}
+
+/// Runs the days and writes a standalone HTML report of their
+/// timings, with the trends of the history when there is one.
+pub fn html_report(session: Option<&str>, days: &[u32], output: &Path) -> io::Result<()> {
+    let reports: Vec<DayReport> = select_days(days)
+        .into_iter()
+        .map(|day| day.run(session))
+        .collect();
+    let entries = match history::load(Path::new(history::HISTORY_FILE)) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+        entries => entries?,
+    };
+
+    std::fs::write(output, html::render(&reports, &entries))
+}
+
+/// Solves an input in its own thread, on a new rayon pool of `threads`
+/// if given. The thread of a day running past the timeout is left
+/// behind, it ends with the process.
+fn solve_with_timeout(
+    resolve: fn(Input) -> (String, String),
+    input: String,
+    threads: Option<usize>,
+    timeout: Duration,
+) -> Result<(String, String), verify::Outcome> {
+    let (sender, receiver) = std::sync::mpsc::channel();
+
+    std::thread::spawn(move || {
+        let answers = match threads {
+            None => catch_resolve(resolve, &input),
+            Some(threads) => rayon::ThreadPoolBuilder::new()
+                .num_threads(threads)
+                .build()
+                .map_err(|e| e.to_string())
+                .and_then(|pool| pool.install(|| catch_resolve(resolve, &input))),
+        };
+
+        let _ = sender.send(answers);
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok(answers) => answers.map_err(verify::Outcome::Panic),
+        Err(_) => Err(verify::Outcome::Timeout(timeout)),
+    }
+}
+
+/// Checks the answers of the days against the ones accepted on their
+/// puzzle page. A day running longer than `timeout` is abandoned.
+///
+/// With `repeat` above 1, the days are solved that many times on
+/// pools of 1, 2, 4... threads to find the answers depending on the
+/// scheduling. As the hash seeds of the standard maps differ from one
+/// thread to another, each run also gets new ones.
+pub fn verify(
+    session: Option<&str>,
+    days: &[u32],
+    timeout: Duration,
+    repeat: usize,
+) -> Vec<verify::Case> {
+    let max_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let counts = thread_counts(max_threads.max(4));
+
+    without_panic_messages(|| {
+        select_days(days)
+            .into_iter()
+            .flat_map(|day| {
+                let day_number = day.parse_number();
+                let known = read_puzzle_markdown(session, day_number)
+                    .map(|markdown| puzzle::Puzzle::from_markdown(&markdown).answers)
+                    .unwrap_or_default();
+                let input = match read_input(session, day_number) {
+                    Ok(input) => input,
+                    Err(e) => {
+                        let outcome =
+                            verify::Outcome::Panic(format!("unable to read the input: {e}"));
+
+                        return verify::cases(day_number, &known, &[Err(outcome)], Duration::ZERO);
+                    }
+                };
+                let start = Instant::now();
+                let mut runs = vec![];
+
+                for run in 0..repeat.max(1) {
+                    let threads = (repeat > 1).then(|| counts[run % counts.len()]);
+                    let answers = solve_with_timeout(day.resolve, input.clone(), threads, timeout);
+                    let timed_out = matches!(answers, Err(verify::Outcome::Timeout(_)));
+
+                    runs.push(answers);
+                    if timed_out {
+                        break;
+                    }
+                }
+
+                let duration = start.elapsed() / runs.len() as u32;
+
+                verify::cases(day_number, &known, &runs, duration)
+            })
+            .collect()
+    })
+}
+
+/// Modification times of files, None for a missing one.
+fn modification_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+fn print_answers(name: &str, resolve: fn(Input) -> (String, String), input: io::Result<String>) {
+    let input = match input {
+        Ok(input) => input,
+        Err(e) => return println!("{name}: unable to read: {e}"),
+    };
+    let start = Instant::now();
+
+    match without_panic_messages(|| catch_resolve(resolve, &input)) {
+        Ok((part1, part2)) => println!(
+            "{name}: part1: {part1:20} part2: {part2:20} in {:?}",
+            start.elapsed()
+        ),
+        Err(message) => println!("{name}: panicked: {message}"),
+    }
+}
+
+/// Cargo command on the sources this binary was built from, with the
+/// same profile.
+fn cargo(subcommand: &str) -> std::process::Command {
+    let mut command = std::process::Command::new("cargo");
+
+    command
+        .arg(subcommand)
+        .arg("--manifest-path")
+        .arg(Path::new(env!("CARGO_MANIFEST_DIR")).join("Cargo.toml"))
+        .args(["--quiet", "--bin", "advent_2025"]);
+    if !cfg!(debug_assertions) {
+        command.arg("--release");
+    }
+
+    command
+}
+
+fn cargo_output(command: &mut std::process::Command) -> Result<(), String> {
+    let output = command
+        .output()
+        .map_err(|e| format!("unable to run cargo: {e}"))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(String::from_utf8_lossy(&output.stdout).into_owned()
+            + &String::from_utf8_lossy(&output.stderr))
+    }
+}
+
+/// Runs the tests of a day, with the examples of the puzzle, through
+/// cargo so that they follow the changes of the source. Gives the
+/// output of cargo on a failure.
+fn example_checks(day_number: u32) -> Result<(), String> {
+    cargo_output(cargo("test").arg(format!("days::day{day_number:0>2}::")))
+}
+
+/// Rebuilds the binary and runs the new one with the same arguments
+/// in place of this process. Gives the output of cargo if the build
+/// fails.
+fn restart_rebuilt() -> Result<std::convert::Infallible, String> {
+    cargo_output(&mut cargo("build"))?;
+
+    let mut command = cargo("run");
+
+    command.arg("--").args(std::env::args_os().skip(1));
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+
+        Err(format!("unable to run the new build: {}", command.exec()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let status = command
+            .status()
+            .map_err(|e| format!("unable to run the new build: {e}"))?;
+
+        std::process::exit(status.code().unwrap_or(1))
+    }
+}
+
+/// Solves the input of a day, and the scratch input if any, then does
+/// it again whenever one of them changes. The example checks are run
+/// each time. When the source of the day changes, the binary is
+/// rebuilt and restarted so that the answers come from the new code.
+pub fn watch(session: Option<&str>, day_number: u32, scratch: Option<&Path>) -> ! {
+    const SOURCE: usize = 1;
+
+    let day = select_days(&[day_number])[0];
+    let mut paths = vec![
+        input_path(day_number),
+        Path::new(env!("CARGO_MANIFEST_DIR")).join(day.day_filename),
+    ];
+
+    paths.extend(scratch.map(Path::to_path_buf));
+
+    // Taken before the run to catch the changes made meanwhile.
+    let mut times = modification_times(&paths);
+
+    loop {
+        println!("--- day{day_number:0>2}");
+        trace::enter(day_number);
+        print_answers("input", day.resolve, read_input(session, day_number));
+        if let Some(scratch) = scratch {
+            print_answers(
+                &scratch.display().to_string(),
+                day.resolve,
+                std::fs::read_to_string(scratch),
+            );
+        }
+        trace::leave();
+        match example_checks(day_number) {
+            Ok(()) => println!("examples: ok"),
+            Err(output) => print!("examples: FAILED\n{output}"),
+        }
+        // Watching only ends with a kill.
+        profile::save();
+
+        loop {
+            let previous = times;
+
+            while modification_times(&paths) == previous {
+                std::thread::sleep(Duration::from_millis(500));
+            }
+            times = modification_times(&paths);
+
+            if times[SOURCE] == previous[SOURCE] {
+                break;
+            }
+
+            println!("--- rebuilding");
+            let Err(output) = restart_rebuilt();
+
+            // Solving with the old code would be misleading.
+            print!("build failed\n{output}");
+        }
+    }
 }
 
 inventory::collect!(Day);
+inventory::collect!(Variant);
+inventory::collect!(Generator);
+inventory::collect!(Sections);
@@ -1,22 +1,210 @@
-use clap::Parser;
+use advent_2025::history;
+use advent_2025::interrupt;
+use advent_2025::profile;
+use advent_2025::progress;
+use advent_2025::report::Format;
+use advent_2025::trace;
+use advent_2025::verify::{self, Style};
+use clap::{CommandFactory, Parser, Subcommand};
 use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 mod days;
 
+#[derive(Subcommand)]
+enum Command {
+    #[command(about = "solve a day")]
+    Run {
+        day: u32,
+        #[arg(
+            long,
+            help = "solve again when the input, the scratch input or the source changes"
+        )]
+        watch: bool,
+        #[arg(long, requires = "watch", help = "another input to solve")]
+        scratch: Option<PathBuf>,
+    },
+    #[command(about = "run every variant of the days and compare their answers")]
+    Compare { days: Vec<u32> },
+    #[command(about = "generate a random input for a day")]
+    Gen {
+        day: u32,
+        #[arg(long, default_value_t = 1)]
+        seed: u64,
+        #[arg(
+            long,
+            default_value_t = 100,
+            help = "size of the input, its unit depends on the day"
+        )]
+        scale: usize,
+        #[arg(long, help = "solve the generated input instead of printing it")]
+        run: bool,
+    },
+    #[command(about = "compare the days with their variants on generated inputs")]
+    Fuzz {
+        days: Vec<u32>,
+        #[arg(long, default_value_t = 100, help = "number of seeds to try")]
+        seeds: u64,
+        #[arg(long, default_value_t = 100)]
+        scale: usize,
+    },
+    #[command(about = "time the days over several runs")]
+    Bench {
+        days: Vec<u32>,
+        #[arg(long, default_value_t = 5)]
+        runs: usize,
+        #[arg(long, help = "run the days with 1, 2, 4... threads")]
+        scaling: bool,
+        #[arg(
+            long,
+            value_name = "N",
+            requires = "scaling",
+            help = "most threads of the scaling, all the cores by default"
+        )]
+        max_threads: Option<usize>,
+    },
+    #[command(about = "show the timing trends of the previous runs")]
+    History {
+        days: Vec<u32>,
+        #[arg(
+            long,
+            default_value_t = 10.0,
+            help = "percentage above the recent median flagged as a regression"
+        )]
+        threshold: f64,
+        #[arg(
+            long,
+            default_value_t = 5,
+            help = "number of previous runs in the median"
+        )]
+        window: usize,
+    },
+    #[command(about = "print an Org table of the results of every day")]
+    Report {
+        #[arg(long, help = "write the table in the results section of README.org")]
+        readme: bool,
+        #[arg(
+            long,
+            help = "use the last recorded timings instead of running the days"
+        )]
+        recorded: bool,
+    },
+    #[command(about = "write a standalone HTML report of the timings of the days")]
+    Html {
+        days: Vec<u32>,
+        #[arg(long, default_value = "report.html")]
+        output: PathBuf,
+    },
+    #[command(about = "check the answers against the ones accepted on the puzzle pages")]
+    Verify {
+        days: Vec<u32>,
+        #[arg(
+            long,
+            default_value_t = 60,
+            help = "seconds after which a day is abandoned"
+        )]
+        timeout: u64,
+        #[arg(long, default_value = "text", help = "text, junit or tap")]
+        report: Style,
+        #[arg(
+            long,
+            default_value_t = 1,
+            help = "solve each day N times with various thread counts and report unstable answers"
+        )]
+        repeat: usize,
+    },
+    #[command(about = "interactive table of the days")]
+    Tui,
+    #[command(about = "minimize an input making a day panic or disagree with its variants")]
+    Shrink {
+        day: u32,
+        #[arg(long, help = "input file, the day input by default")]
+        input: Option<PathBuf>,
+        #[arg(long, conflicts_with = "input", help = "shrink a generated input")]
+        seed: Option<u64>,
+        #[arg(long, default_value_t = 100)]
+        scale: usize,
+        #[arg(
+            long,
+            default_value = "shrunk.rs",
+            help = "where to write the regression test"
+        )]
+        output: PathBuf,
+    },
+}
+
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Args {
-    #[arg(short, long, help = "limit the number of threads used by rayon")]
+    #[arg(
+        short,
+        long,
+        global = true,
+        help = "limit the number of threads used by rayon"
+    )]
     nthreads: Option<usize>,
     #[arg(
         short,
         long,
+        global = true,
         help = "Advent Of Code session ID for automatic downloading of inputs"
     )]
     session: Option<String>,
-    #[arg(trailing_var_arg = true)]
+    #[arg(
+        long,
+        global = true,
+        default_value = "text",
+        help = "output format of the run report: text or json"
+    )]
+    format: Format,
+    #[arg(
+        short,
+        long,
+        global = true,
+        action = clap::ArgAction::Count,
+        help = "print the events of the days solved without a command, by run or by compare, more with -vv and -vvv"
+    )]
+    verbose: u8,
+    #[arg(
+        long = "trace",
+        global = true,
+        value_name = "DAY",
+        help = "only print the events of this day, may be repeated"
+    )]
+    trace_days: Vec<u32>,
+    #[arg(
+        long,
+        global = true,
+        help = "write the events to a file instead of the standard error, implies -v"
+    )]
+    trace_file: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        help = "write the timings of the run as Chrome trace events"
+    )]
+    chrome_trace: Option<PathBuf>,
+    #[arg(
+        long,
+        global = true,
+        requires = "chrome_trace",
+        help = "also trace the tasks inside the days"
+    )]
+    trace_tasks: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
     days: Vec<u32>,
 }
 
+/// Exits without losing the Chrome trace.
+fn exit(code: i32) -> ! {
+    profile::save();
+    std::process::exit(code)
+}
+
 fn main() {
     let args = Args::parse();
 
@@ -27,7 +215,179 @@ fn main() {
             .unwrap();
     }
 
+    // The events are only followed where the days are solved one at a
+    // time.
+    let tracing = args.verbose > 0 || !args.trace_days.is_empty() || args.trace_file.is_some();
+    let traced = matches!(
+        args.command,
+        None | Some(Command::Run { .. } | Command::Compare { .. })
+    );
+
+    if tracing && !traced {
+        Args::command()
+            .error(
+                clap::error::ErrorKind::ArgumentConflict,
+                "-v and --trace only apply to the days solved without a command, by run or by compare",
+            )
+            .exit();
+    }
+
+    if tracing {
+        let output = args.trace_file.map(|path| {
+            fs::File::create(&path)
+                .unwrap_or_else(|e| panic!("unable to create {}: {e}", path.display()))
+        });
+
+        trace::init(
+            trace::Level::from_verbosity(args.verbose),
+            args.trace_days,
+            output,
+        );
+    }
+
+    // The status line would get in the way of the redirected output.
+    if args.format == Format::Text && io::stdout().is_terminal() && io::stderr().is_terminal() {
+        progress::enable();
+    }
+
+    if let Some(path) = args.chrome_trace {
+        profile::start(args.trace_tasks, path);
+    }
+
     let session = args.session.or(env::var("AOC_SESSION").ok());
 
-    advent_2025::resolve(session.as_deref(), &args.days);
+    match args.command {
+        None => {
+            if !advent_2025::resolve(session.as_deref(), &args.days, args.format) {
+                exit(interrupt::EXIT_CODE);
+            }
+        }
+        Some(Command::Run {
+            day,
+            watch,
+            scratch,
+        }) => {
+            if watch {
+                advent_2025::watch(session.as_deref(), day, scratch.as_deref());
+            } else if !advent_2025::resolve(session.as_deref(), &[day], args.format) {
+                exit(interrupt::EXIT_CODE);
+            }
+        }
+        Some(Command::Compare { days }) => {
+            if !advent_2025::compare(session.as_deref(), &days) {
+                exit(1);
+            }
+        }
+        Some(Command::Gen {
+            day,
+            seed,
+            scale,
+            run,
+        }) => {
+            if run {
+                advent_2025::generate_and_resolve(day, seed, scale);
+            } else {
+                print!("{}", advent_2025::generate(day, seed, scale));
+            }
+        }
+        Some(Command::Fuzz { days, seeds, scale }) => {
+            if !advent_2025::fuzz(&days, 0..seeds, scale) {
+                exit(1);
+            }
+        }
+        Some(Command::Bench {
+            days,
+            runs,
+            scaling,
+            max_threads,
+        }) => {
+            let scaling = scaling.then(|| {
+                max_threads
+                    .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |n| n.get()))
+            });
+
+            advent_2025::bench(session.as_deref(), &days, runs, scaling);
+        }
+        Some(Command::History {
+            days,
+            threshold,
+            window,
+        }) => {
+            let path = Path::new(history::HISTORY_FILE);
+            let entries = match history::load(path) {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+                Err(e) => panic!("unable to read {}: {e}", path.display()),
+            };
+
+            if entries.is_empty() {
+                println!("no recorded runs");
+            } else if !history::check(&entries, &days, window, threshold / 100.0) {
+                exit(1);
+            }
+        }
+        Some(Command::Report { readme, recorded }) => {
+            let table = advent_2025::results_table(session.as_deref(), recorded).unwrap();
+
+            if readme {
+                advent_2025::update_readme(Path::new("README.org"), &table).unwrap();
+            } else {
+                print!("{table}");
+            }
+        }
+        Some(Command::Html { days, output }) => {
+            advent_2025::html_report(session.as_deref(), &days, &output)
+                .unwrap_or_else(|e| panic!("unable to write {}: {e}", output.display()));
+        }
+        Some(Command::Verify {
+            days,
+            timeout,
+            report,
+            repeat,
+        }) => {
+            let cases = advent_2025::verify(
+                session.as_deref(),
+                &days,
+                Duration::from_secs(timeout),
+                repeat,
+            );
+
+            print!(
+                "{}",
+                match report {
+                    Style::Text => verify::text(&cases),
+                    Style::Junit => verify::junit(&cases),
+                    Style::Tap => verify::tap(&cases),
+                }
+            );
+
+            if cases.iter().any(verify::Case::failed) {
+                exit(1);
+            }
+        }
+        Some(Command::Tui) => {
+            advent_2025::tui::run(session.as_deref())
+                .unwrap_or_else(|e| panic!("unable to run the dashboard: {e}"));
+        }
+        Some(Command::Shrink {
+            day,
+            input,
+            seed,
+            scale,
+            output,
+        }) => {
+            let input = match (input, seed) {
+                (Some(path), _) => fs::read_to_string(path),
+                (None, Some(seed)) => Ok(advent_2025::generate(day, seed, scale)),
+                (None, None) => advent_2025::read_input(session.as_deref(), day),
+            }
+            .unwrap_or_else(|e| panic!("unable to read the input: {e}"));
+
+            if !advent_2025::shrink(day, &input, &output).unwrap() {
+                exit(1);
+            }
+        }
+    }
+
+    profile::save();
 }
@@ -0,0 +1,369 @@
+use std::fmt;
+
+/// Integers that can be built digit by digit with overflow checks.
+pub trait Integer: Copy {
+    const SIGNED: bool;
+    const ZERO: Self;
+    /// Number of digits that can never overflow.
+    const SAFE_DIGITS: usize;
+
+    /// Computes `self * 10 + digit`.
+    fn push_digit(self, digit: u8) -> Option<Self>;
+
+    /// Computes `self * 10 - digit`, used to reach the minimum value
+    /// of signed integers.
+    fn push_negative_digit(self, digit: u8) -> Option<Self>;
+
+    fn wrapping_push_digit(self, digit: u8) -> Self;
+
+    fn wrapping_push_negative_digit(self, digit: u8) -> Self;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal, $($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const SIGNED: bool = $signed;
+                const ZERO: Self = 0;
+                const SAFE_DIGITS: usize = <$t>::MAX.ilog10() as usize;
+
+                #[inline]
+                fn push_digit(self, digit: u8) -> Option<Self> {
+                    self.checked_mul(10)?.checked_add(digit as Self)
+                }
+
+                #[inline]
+                fn push_negative_digit(self, digit: u8) -> Option<Self> {
+                    self.checked_mul(10)?.checked_sub(digit as Self)
+                }
+
+                #[inline]
+                fn wrapping_push_digit(self, digit: u8) -> Self {
+                    self.wrapping_mul(10).wrapping_add(digit as Self)
+                }
+
+                #[inline]
+                fn wrapping_push_negative_digit(self, digit: u8) -> Self {
+                    self.wrapping_mul(10).wrapping_sub(digit as Self)
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(false, u8, u16, u32, u64, u128, usize);
+impl_integer!(true, i8, i16, i32, i64, i128, isize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseIntErrorKind {
+    Empty,
+    InvalidDigit(u8),
+    Overflow,
+}
+
+/// The offset is the one of the invalid byte, or the start of the
+/// number for empty numbers and overflows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ParseIntError {
+    pub kind: ParseIntErrorKind,
+    pub offset: usize,
+}
+
+impl ParseIntError {
+    fn new(kind: ParseIntErrorKind, offset: usize) -> Self {
+        ParseIntError { kind, offset }
+    }
+
+    fn shift(self, offset: usize) -> Self {
+        ParseIntError::new(self.kind, self.offset + offset)
+    }
+}
+
+impl fmt::Display for ParseIntError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let offset = self.offset;
+
+        match self.kind {
+            ParseIntErrorKind::Empty => write!(f, "empty number at byte {offset}"),
+            ParseIntErrorKind::InvalidDigit(c) => {
+                write!(f, "invalid digit {:?} at byte {offset}", c as char)
+            }
+            ParseIntErrorKind::Overflow => write!(f, "number too large at byte {offset}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseIntError {}
+
+/// Parses a whole slice as a decimal integer. A leading `+` is
+/// accepted, and a leading `-` for signed integers.
+pub fn parse<T>(bytes: &[u8]) -> Result<T, ParseIntError>
+where
+    T: Integer,
+{
+    let (negative, start) = match bytes.first() {
+        Some(b'-') if T::SIGNED => (true, 1),
+        Some(b'+') => (false, 1),
+        _ => (false, 0),
+    };
+
+    if start == bytes.len() {
+        return Err(ParseIntError::new(ParseIntErrorKind::Empty, start));
+    }
+
+    let mut n = T::ZERO;
+
+    // Short numbers can't overflow, let's skip the checks.
+    if bytes.len() - start <= T::SAFE_DIGITS {
+        for (i, &c) in bytes.iter().enumerate().skip(start) {
+            let digit = c.wrapping_sub(b'0');
+
+            if digit > 9 {
+                return Err(ParseIntError::new(ParseIntErrorKind::InvalidDigit(c), i));
+            }
+
+            n = if negative {
+                n.wrapping_push_negative_digit(digit)
+            } else {
+                n.wrapping_push_digit(digit)
+            };
+        }
+
+        return Ok(n);
+    }
+
+    for (i, &c) in bytes.iter().enumerate().skip(start) {
+        let digit = c.wrapping_sub(b'0');
+
+        if digit > 9 {
+            return Err(ParseIntError::new(ParseIntErrorKind::InvalidDigit(c), i));
+        }
+
+        n = if negative {
+            n.push_negative_digit(digit)
+        } else {
+            n.push_digit(digit)
+        }
+        .ok_or(ParseIntError::new(ParseIntErrorKind::Overflow, 0))?;
+    }
+
+    Ok(n)
+}
+
+/// Parses digits coming from an iterator, for numbers that are not
+/// stored contiguously (a grid column for example). Offsets are
+/// positions in the iterator.
+pub fn parse_digits<T, I>(digits: I) -> Result<T, ParseIntError>
+where
+    T: Integer,
+    I: IntoIterator<Item = u8>,
+{
+    let mut n = None;
+
+    for (i, c) in digits.into_iter().enumerate() {
+        let digit = c.wrapping_sub(b'0');
+
+        if digit > 9 {
+            return Err(ParseIntError::new(ParseIntErrorKind::InvalidDigit(c), i));
+        }
+
+        n = Some(
+            n.unwrap_or(T::ZERO)
+                .push_digit(digit)
+                .ok_or(ParseIntError::new(ParseIntErrorKind::Overflow, 0))?,
+        );
+    }
+
+    n.ok_or(ParseIntError::new(ParseIntErrorKind::Empty, 0))
+}
+
+#[test]
+fn check_parse() {
+    assert_eq!(parse::<u32>(b"0"), Ok(0));
+    assert_eq!(parse::<u32>(b"+42"), Ok(42));
+    assert_eq!(parse::<u64>(b"18446744073709551615"), Ok(u64::MAX));
+    assert_eq!(parse::<i8>(b"-128"), Ok(i8::MIN));
+    assert_eq!(parse::<i8>(b"127"), Ok(i8::MAX));
+    assert_eq!(parse::<i8>(b"-99"), Ok(-99));
+    assert_eq!(parse::<u32>(b"0004294967295"), Ok(u32::MAX));
+
+    assert_eq!(
+        parse::<u8>(b"256"),
+        Err(ParseIntError::new(ParseIntErrorKind::Overflow, 0))
+    );
+    assert_eq!(
+        parse::<i8>(b"-129"),
+        Err(ParseIntError::new(ParseIntErrorKind::Overflow, 0))
+    );
+    assert_eq!(
+        parse::<u32>(b"-1"),
+        Err(ParseIntError::new(ParseIntErrorKind::InvalidDigit(b'-'), 0))
+    );
+    assert_eq!(
+        parse::<u32>(b"12a4"),
+        Err(ParseIntError::new(ParseIntErrorKind::InvalidDigit(b'a'), 2))
+    );
+    assert_eq!(
+        parse::<u32>(b""),
+        Err(ParseIntError::new(ParseIntErrorKind::Empty, 0))
+    );
+    assert_eq!(
+        parse::<i32>(b"-"),
+        Err(ParseIntError::new(ParseIntErrorKind::Empty, 1))
+    );
+}
+
+#[test]
+fn check_parse_digits() {
+    assert_eq!(parse_digits::<u64, _>(*b"1234"), Ok(1234));
+    assert_eq!(
+        parse_digits::<u64, _>(*b"12 4"),
+        Err(ParseIntError::new(ParseIntErrorKind::InvalidDigit(b' '), 2))
+    );
+    assert_eq!(
+        parse_digits::<u64, _>([]),
+        Err(ParseIntError::new(ParseIntErrorKind::Empty, 0))
+    );
+}
+
+/// Iterator over all the integers found in a line, see [`integers`].
+pub struct Integers<'a, T> {
+    bytes: &'a [u8],
+    offset: usize,
+    _marker: std::marker::PhantomData<T>,
+}
+
+/// Extracts all the integers of a line, ignoring everything
+/// else. For signed integers, a `-` directly in front of digits is
+/// a sign unless it follows a digit, so that `3-5` gives 3 and 5.
+pub fn integers<T>(bytes: &[u8]) -> Integers<'_, T>
+where
+    T: Integer,
+{
+    Integers {
+        bytes,
+        offset: 0,
+        _marker: std::marker::PhantomData,
+    }
+}
+
+impl<T> Iterator for Integers<'_, T>
+where
+    T: Integer,
+{
+    type Item = Result<T, ParseIntError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.bytes;
+        let mut start = self.offset + bytes[self.offset..].iter().position(u8::is_ascii_digit)?;
+        let end = start
+            + bytes[start..]
+                .iter()
+                .position(|c| !c.is_ascii_digit())
+                .unwrap_or(bytes.len() - start);
+
+        if T::SIGNED
+            && start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_digit())
+        {
+            start -= 1;
+        }
+
+        self.offset = end;
+
+        Some(parse(&bytes[start..end]).map_err(|e| e.shift(start)))
+    }
+}
+
+#[test]
+fn check_integers() {
+    assert_eq!(
+        integers::<u32>(b"12x5: 1 0 13").collect::<Result<Vec<_>, _>>(),
+        Ok(vec![12, 5, 1, 0, 13])
+    );
+    assert_eq!(
+        integers::<i32>(b"p=-3,4 v=3-5 -").collect::<Result<Vec<_>, _>>(),
+        Ok(vec![-3, 4, 3, 5])
+    );
+    assert_eq!(
+        integers::<u32>(b"-3").collect::<Result<Vec<_>, _>>(),
+        Ok(vec![3])
+    );
+    assert_eq!(integers::<u32>(b"no numbers").count(), 0);
+    assert_eq!(
+        integers::<u8>(b"a 12 300").collect::<Result<Vec<_>, _>>(),
+        Err(ParseIntError::new(ParseIntErrorKind::Overflow, 5))
+    );
+}
+
+/// Iterator parsing every field between separators, see [`split_parse`].
+pub struct SplitParse<'a, T> {
+    bytes: &'a [u8],
+    separator: u8,
+    offset: Option<usize>,
+    _marker: std::marker::PhantomData<T>,
+}
+
+/// Splits on `separator` and parses every field, surrounding ASCII
+/// whitespaces being ignored. Offsets are relative to `bytes`.
+pub fn split_parse<T>(bytes: &[u8], separator: u8) -> SplitParse<'_, T>
+where
+    T: Integer,
+{
+    SplitParse {
+        bytes,
+        separator,
+        offset: Some(0),
+        _marker: std::marker::PhantomData,
+    }
+}
+
+impl<T> Iterator for SplitParse<'_, T>
+where
+    T: Integer,
+{
+    type Item = Result<T, ParseIntError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.offset?;
+        let field = &self.bytes[start..];
+        let end = match field.iter().position(|&c| c == self.separator) {
+            Some(len) => {
+                self.offset = Some(start + len + 1);
+                start + len
+            }
+            None => {
+                self.offset = None;
+                self.bytes.len()
+            }
+        };
+        let field = &self.bytes[start..end];
+        let leading = field
+            .iter()
+            .position(|c| !c.is_ascii_whitespace())
+            .unwrap_or(field.len());
+
+        Some(parse(field[leading..].trim_ascii_end()).map_err(|e| e.shift(start + leading)))
+    }
+}
+
+#[test]
+fn check_split_parse() {
+    assert_eq!(
+        split_parse::<u32>(b"3,5,4,7", b',').collect::<Result<Vec<_>, _>>(),
+        Ok(vec![3, 5, 4, 7])
+    );
+    assert_eq!(
+        split_parse::<i64>(b"1, -2 ,3", b',').collect::<Result<Vec<_>, _>>(),
+        Ok(vec![1, -2, 3])
+    );
+    assert_eq!(
+        split_parse::<u32>(b"1,2,a", b',').collect::<Result<Vec<_>, _>>(),
+        Err(ParseIntError::new(ParseIntErrorKind::InvalidDigit(b'a'), 4))
+    );
+    assert_eq!(
+        split_parse::<u32>(b"1,,2", b',').collect::<Result<Vec<_>, _>>(),
+        Err(ParseIntError::new(ParseIntErrorKind::Empty, 2))
+    );
+}
@@ -0,0 +1,173 @@
+use crate::report::json_string;
+use std::fmt::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Time interval of the run, shown as a slice in a trace viewer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Span {
+    pub name: String,
+    pub category: &'static str,
+    /// Offset from the start of the recording.
+    pub start: Duration,
+    pub duration: Duration,
+    /// 0 for the main thread, then the rayon workers.
+    pub thread: usize,
+}
+
+struct Recording {
+    start: Instant,
+    spans: Vec<Span>,
+    output: PathBuf,
+}
+
+const OFF: u8 = 0;
+const DAYS: u8 = 1;
+const TASKS: u8 = 2;
+
+static DETAIL: AtomicU8 = AtomicU8::new(OFF);
+static RECORDING: Mutex<Option<Recording>> = Mutex::new(None);
+
+fn thread() -> usize {
+    rayon::current_thread_index().map_or(0, |i| i + 1)
+}
+
+/// Starts recording the spans of the days, and of the tasks inside
+/// the days when `tasks` is set, to be saved to `output`.
+pub fn start(tasks: bool, output: PathBuf) {
+    *RECORDING.lock().unwrap() = Some(Recording {
+        start: Instant::now(),
+        spans: vec![],
+        output,
+    });
+    DETAIL.store(if tasks { TASKS } else { DAYS }, Ordering::Relaxed);
+}
+
+pub(crate) fn recording() -> bool {
+    DETAIL.load(Ordering::Relaxed) != OFF
+}
+
+pub(crate) fn record(name: String, category: &'static str, start: Instant, end: Instant) {
+    if let Some(recording) = RECORDING.lock().unwrap().as_mut() {
+        recording.spans.push(Span {
+            name,
+            category,
+            start: start.saturating_duration_since(recording.start),
+            duration: end.saturating_duration_since(start),
+            thread: thread(),
+        });
+    }
+}
+
+/// Writes the spans recorded so far as Chrome trace events, the whole
+/// of it being the "run" span. The recording goes on, this is meant to
+/// be called before any exit. Does nothing unless recording.
+pub fn save() {
+    let recording = RECORDING.lock().unwrap();
+    let Some(recording) = recording.as_ref() else {
+        return;
+    };
+    let mut spans = recording.spans.clone();
+
+    spans.push(Span {
+        name: "run".to_string(),
+        category: "run",
+        start: Duration::ZERO,
+        duration: recording.start.elapsed(),
+        thread: 0,
+    });
+
+    if let Err(e) = std::fs::write(&recording.output, chrome_json(&spans)) {
+        eprintln!("unable to write {}: {e}", recording.output.display());
+    }
+}
+
+/// Records a span from its creation to its drop, when the tasks are
+/// recorded. Meant for the work items of a day, like
+/// `let _task = profile::task("machine");`.
+pub struct Task {
+    name: &'static str,
+    start: Option<Instant>,
+}
+
+pub fn task(name: &'static str) -> Task {
+    Task {
+        name,
+        start: (DETAIL.load(Ordering::Relaxed) == TASKS).then(Instant::now),
+    }
+}
+
+impl Drop for Task {
+    fn drop(&mut self) {
+        if let Some(start) = self.start {
+            record(self.name.to_string(), "task", start, Instant::now());
+        }
+    }
+}
+
+/// Chrome Trace Event format, as complete events in microseconds.
+pub fn chrome_json(spans: &[Span]) -> String {
+    let mut threads: Vec<usize> = spans.iter().map(|s| s.thread).collect();
+
+    threads.sort_unstable();
+    threads.dedup();
+
+    let mut events: Vec<String> = threads
+        .into_iter()
+        .map(|thread| {
+            let name = if thread == 0 {
+                "main".to_string()
+            } else {
+                format!("rayon worker {}", thread - 1)
+            };
+
+            format!(
+                "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\"tid\":{thread},\"args\":{{\"name\":{}}}}}",
+                json_string(&name)
+            )
+        })
+        .collect();
+
+    for span in spans {
+        let mut event = String::new();
+
+        let _ = write!(
+            event,
+            "{{\"name\":{},\"cat\":{},\"ph\":\"X\",\"ts\":{:.3},\"dur\":{:.3},\"pid\":1,\"tid\":{}}}",
+            json_string(&span.name),
+            json_string(span.category),
+            span.start.as_secs_f64() * 1e6,
+            span.duration.as_secs_f64() * 1e6,
+            span.thread
+        );
+        events.push(event);
+    }
+
+    format!(
+        "{{\"traceEvents\":[\n{}\n],\"displayTimeUnit\":\"ms\"}}\n",
+        events.join(",\n")
+    )
+}
+
+#[test]
+fn check_chrome_json() {
+    let spans = [Span {
+        name: "day01".to_string(),
+        category: "day",
+        start: Duration::from_micros(3),
+        duration: Duration::from_nanos(1500),
+        thread: 2,
+    }];
+
+    assert_eq!(
+        chrome_json(&spans),
+        r#"{"traceEvents":[
+{"name":"thread_name","ph":"M","pid":1,"tid":2,"args":{"name":"rayon worker 1"}},
+{"name":"day01","cat":"day","ph":"X","ts":3.000,"dur":1.500,"pid":1,"tid":2}
+],"displayTimeUnit":"ms"}
+"#
+    );
+    assert!(task("ignored").start.is_none());
+}
@@ -0,0 +1,115 @@
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static DONE: AtomicUsize = AtomicUsize::new(0);
+static TOTAL: AtomicUsize = AtomicUsize::new(0);
+static UNIT: Mutex<&'static str> = Mutex::new("");
+
+/// Days running for less than this never show their progress.
+const DELAY: Duration = Duration::from_millis(300);
+const REFRESH: Duration = Duration::from_millis(100);
+
+/// Announces the work of the running day, like `total(n, "machines")`.
+pub fn total(count: usize, unit: &'static str) {
+    if ENABLED.load(Ordering::Relaxed) {
+        *UNIT.lock().unwrap() = unit;
+        DONE.store(0, Ordering::Relaxed);
+        TOTAL.store(count, Ordering::Relaxed);
+    }
+}
+
+/// One more unit of work done, may be called from any thread.
+pub fn step() {
+    if ENABLED.load(Ordering::Relaxed) {
+        DONE.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Shows the progress of the days on a status line of the standard
+/// error. Only meant for a terminal.
+pub fn enable() {
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+fn status_line(day_number: u32) -> Option<String> {
+    let total = TOTAL.load(Ordering::Relaxed);
+
+    (total > 0).then(|| {
+        format!(
+            "day{day_number:0>2}: {} of {total} {}",
+            DONE.load(Ordering::Relaxed),
+            UNIT.lock().unwrap()
+        )
+    })
+}
+
+/// Redraws the status line of a day until dropped.
+pub(crate) struct Display {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Display {
+    pub(crate) fn start(day_number: u32) -> Option<Self> {
+        if !ENABLED.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        TOTAL.store(0, Ordering::Relaxed);
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        let start = Instant::now();
+        let thread = thread::spawn(move || {
+            let mut shown = false;
+
+            while !stopped.load(Ordering::Relaxed) {
+                thread::park_timeout(REFRESH);
+
+                if start.elapsed() >= DELAY && !stopped.load(Ordering::Relaxed) {
+                    if let Some(line) = status_line(day_number) {
+                        eprint!("\r\x1b[K{line}");
+                        let _ = io::stderr().flush();
+                        shown = true;
+                    }
+                }
+            }
+
+            if shown {
+                eprint!("\r\x1b[K");
+            }
+        });
+
+        Some(Display {
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for Display {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+        TOTAL.store(0, Ordering::Relaxed);
+    }
+}
+
+#[test]
+fn check_status_line() {
+    total(3, "ignored");
+    assert_eq!(status_line(10), None);
+
+    *UNIT.lock().unwrap() = "machines";
+    DONE.store(1, Ordering::Relaxed);
+    TOTAL.store(3, Ordering::Relaxed);
+    assert_eq!(status_line(10).as_deref(), Some("day10: 1 of 3 machines"));
+    TOTAL.store(0, Ordering::Relaxed);
+}
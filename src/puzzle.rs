@@ -0,0 +1,173 @@
+/// What we know about a puzzle from its cached page.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub title: Option<String>,
+    /// Accepted answers, one per star.
+    pub answers: Vec<String>,
+}
+
+impl Puzzle {
+    pub fn stars(&self) -> usize {
+        self.answers.len()
+    }
+
+    /// Reads the Markdown produced by [`html_to_markdown`].
+    pub fn from_markdown(markdown: &str) -> Self {
+        let mut puzzle = Puzzle::default();
+
+        for line in markdown.lines() {
+            if let Some(header) = line.strip_prefix("## --- Day ") {
+                puzzle.title = header
+                    .trim_end_matches(" ---")
+                    .split_once(": ")
+                    .map(|(_, title)| title.to_string());
+            } else if let Some(answer) = line.strip_prefix("Your puzzle answer was `") {
+                if let Some((answer, _)) = answer.split_once('`') {
+                    puzzle.answers.push(answer.to_string());
+                }
+            }
+        }
+
+        puzzle
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Converts the elements of a puzzle page we care about, the
+/// descriptions and the accepted answers, to Markdown.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut rest = html;
+
+    // Articles and answers, in the order of the page.
+    loop {
+        let article = rest.find("<article");
+        let answer = rest.find("<p>Your puzzle answer was");
+        let (start, end_tag) = match (article, answer) {
+            (Some(a), Some(b)) if b < a => (b, "</p>"),
+            (Some(a), _) => (a, "</article>"),
+            (None, Some(b)) => (b, "</p>"),
+            (None, None) => break,
+        };
+        let Some(end) = rest[start..].find(end_tag) else {
+            break;
+        };
+        let end = start + end + end_tag.len();
+
+        convert_element(&rest[start..end], &mut markdown);
+        rest = &rest[end..];
+    }
+
+    markdown.trim_end().to_string() + "\n"
+}
+
+fn convert_element(html: &str, markdown: &mut String) {
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        let text = decode_entities(&rest[..start]);
+
+        // Outside of code blocks, newlines are just spaces.
+        if in_pre {
+            markdown.push_str(&text);
+        } else if markdown.is_empty() || markdown.ends_with('\n') {
+            markdown.push_str(text.replace('\n', " ").trim_start());
+        } else {
+            markdown.push_str(&text.replace('\n', " "));
+        }
+
+        let Some(end) = rest[start..].find('>') else {
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        let name = tag
+            .trim_start_matches('/')
+            .split_whitespace()
+            .next()
+            .unwrap_or("");
+        let closing = tag.starts_with('/');
+
+        match (name, closing) {
+            ("h2", false) => markdown.push_str("## "),
+            ("h2", true) | ("p", true) => markdown.push_str("\n\n"),
+            ("ul", true) => markdown.push('\n'),
+            ("pre", false) => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => markdown.push('`'),
+            ("em", _) => markdown.push('*'),
+            ("li", false) => markdown.push_str("- "),
+            ("li", true) => markdown.push('\n'),
+            _ => {}
+        }
+
+        rest = &rest[start + end + 1..];
+    }
+}
+
+#[cfg(test)]
+const PAGE_TEST: &str = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Secret Entrance ---</h2><p>The dial &lt;starts&gt; at <code>50</code>.
+It <em>clicks</em>:</p>
+<pre><code>L68
+L30
+</code></pre>
+<ul><li>one</li><li>two</li></ul>
+</article>
+<p>Your puzzle answer was <code>1150</code>.</p><article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Count <a href="/x">all</a> clicks.</p></article>
+<p>Your puzzle answer was <code>6738</code>.</p><p>Both parts of this puzzle are complete!</p>
+<form method="post"></form>
+</main></body></html>"#;
+
+#[test]
+fn check_html_to_markdown() {
+    assert_eq!(
+        html_to_markdown(PAGE_TEST),
+        "## --- Day 1: Secret Entrance ---
+
+The dial <starts> at `50`. It *clicks*:
+
+```
+L68
+L30
+```
+
+- one
+- two
+
+Your puzzle answer was `1150`.
+
+## --- Part Two ---
+
+Count all clicks.
+
+Your puzzle answer was `6738`.
+"
+    );
+}
+
+#[test]
+fn check_from_markdown() {
+    let puzzle = Puzzle::from_markdown(&html_to_markdown(PAGE_TEST));
+
+    assert_eq!(puzzle.title.as_deref(), Some("Secret Entrance"));
+    assert_eq!(puzzle.answers, ["1150", "6738"]);
+    assert_eq!(puzzle.stars(), 2);
+    assert_eq!(Puzzle::from_markdown(""), Puzzle::default());
+}
@@ -0,0 +1,94 @@
+use std::ops::RangeInclusive;
+
+/// Small deterministic pseudo random generator (SplitMix64), the same
+/// seed always gives the same sequence.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `range`, both ends included.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+
+        assert!(start <= end, "empty range");
+
+        match (end - start).checked_add(1) {
+            // Multiply-shift reduction, bias is negligible here.
+            Some(len) => start + ((self.next_u64() as u128 * len as u128) >> 64) as u64,
+            None => self.next_u64(),
+        }
+    }
+
+    pub fn range_usize(&mut self, range: RangeInclusive<usize>) -> usize {
+        let (start, end) = range.into_inner();
+
+        self.range(start as u64..=end as u64) as usize
+    }
+
+    /// Returns true with a probability of `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    /// A random item, None if there is none.
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        let last = items.len().checked_sub(1)?;
+
+        Some(&items[self.range_usize(0..=last)])
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.range_usize(0..=i));
+        }
+    }
+}
+
+#[test]
+fn check_rng() {
+    let mut a = Rng::new(42);
+    let mut b = Rng::new(42);
+
+    assert_eq!(
+        (0..10).map(|_| a.next_u64()).collect::<Vec<_>>(),
+        (0..10).map(|_| b.next_u64()).collect::<Vec<_>>()
+    );
+
+    let mut seen = [false; 6];
+
+    for _ in 0..1000 {
+        let v = a.range(3..=8);
+
+        assert!((3..=8).contains(&v));
+        seen[(v - 3) as usize] = true;
+    }
+
+    assert_eq!(seen, [true; 6]);
+    assert_eq!(a.range(7..=7), 7);
+    a.range(0..=u64::MAX);
+
+    let mut items: Vec<u32> = (0..20).collect();
+
+    a.shuffle(&mut items);
+    items.sort_unstable();
+
+    assert_eq!(items, (0..20).collect::<Vec<_>>());
+    assert!(items.contains(a.choose(&items).unwrap()));
+    assert_eq!(a.choose::<u32>(&[]), None);
+}
@@ -0,0 +1,275 @@
+use std::ops::RangeInclusive;
+
+/// A set of `u64` stored as sorted, disjoint and non adjacent
+/// inclusive ranges.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RangeSet {
+    ranges: Vec<(u64, u64)>,
+}
+
+// Pushes a range that doesn't start before the last one, merging it
+// if they overlap or are adjacent.
+fn push_merge(v: &mut Vec<(u64, u64)>, (first, last): (u64, u64)) {
+    if let Some(previous) = v.last_mut() {
+        // No next value after u64::MAX, everything merges.
+        if previous.1.checked_add(1).is_none_or(|next| next >= first) {
+            previous.1 = previous.1.max(last);
+            return;
+        }
+    }
+    v.push((first, last));
+}
+
+impl RangeSet {
+    pub fn new() -> Self {
+        RangeSet::default()
+    }
+
+    /// Builds the set from ranges that are already sorted by their
+    /// start.
+    fn from_sorted<I>(ranges: I) -> Self
+    where
+        I: IntoIterator<Item = (u64, u64)>,
+    {
+        let mut v = vec![];
+
+        for range in ranges {
+            push_merge(&mut v, range);
+        }
+
+        RangeSet { ranges: v }
+    }
+
+    pub fn insert(&mut self, range: RangeInclusive<u64>) {
+        let (first, last) = range.into_inner();
+
+        if first > last {
+            return;
+        }
+
+        // Ranges ending right before first (or after) may merge.
+        let start = self
+            .ranges
+            .partition_point(|&(_, l)| l.checked_add(1).is_some_and(|next| next < first));
+        let end = self
+            .ranges
+            .partition_point(|&(f, _)| f <= last.saturating_add(1));
+
+        if start == end {
+            self.ranges.insert(start, (first, last));
+        } else {
+            let merged = (
+                first.min(self.ranges[start].0),
+                last.max(self.ranges[end - 1].1),
+            );
+
+            self.ranges.splice(start..end, [merged]);
+        }
+    }
+
+    pub fn contains(&self, value: u64) -> bool {
+        let index = self.ranges.partition_point(|&(_, last)| last < value);
+
+        self.ranges
+            .get(index)
+            .is_some_and(|&(first, _)| first <= value)
+    }
+
+    /// Number of values in the set, up to 2^64.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|&(first, last)| (last - first) as u128 + 1)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn ranges(&self) -> impl DoubleEndedIterator<Item = RangeInclusive<u64>> + '_ {
+        self.ranges.iter().map(|&(first, last)| first..=last)
+    }
+
+    /// Iterates over the values missing between the first and the
+    /// last ranges.
+    pub fn gaps(&self) -> impl Iterator<Item = RangeInclusive<u64>> + '_ {
+        self.ranges.windows(2).map(|w| w[0].1 + 1..=w[1].0 - 1)
+    }
+
+    pub fn complement(&self) -> RangeSet {
+        let mut v = vec![];
+        let mut next = Some(0);
+
+        for &(first, last) in &self.ranges {
+            if let Some(n) = next {
+                if n < first {
+                    v.push((n, first - 1));
+                }
+            }
+            next = last.checked_add(1);
+        }
+
+        if let Some(n) = next {
+            v.push((n, u64::MAX));
+        }
+
+        RangeSet { ranges: v }
+    }
+
+    pub fn union(&self, other: &RangeSet) -> RangeSet {
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        RangeSet::from_sorted(std::iter::from_fn(|| match (a.peek(), b.peek()) {
+            (Some(ra), Some(rb)) if ra.0 <= rb.0 => a.next().copied(),
+            (Some(_), Some(_)) => b.next().copied(),
+            (Some(_), None) => a.next().copied(),
+            (None, _) => b.next().copied(),
+        }))
+    }
+
+    pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+        let mut v = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (self.ranges[i], other.ranges[j]);
+            let (first, last) = (a.0.max(b.0), a.1.min(b.1));
+
+            if first <= last {
+                v.push((first, last));
+            }
+
+            // Drop the range ending first, the other one may still
+            // intersect the next ones.
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        RangeSet { ranges: v }
+    }
+
+    pub fn difference(&self, other: &RangeSet) -> RangeSet {
+        self.intersection(&other.complement())
+    }
+}
+
+impl FromIterator<RangeInclusive<u64>> for RangeSet {
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = RangeInclusive<u64>>,
+    {
+        let mut ranges: Vec<(u64, u64)> = iter
+            .into_iter()
+            .map(RangeInclusive::into_inner)
+            .filter(|(first, last)| first <= last)
+            .collect();
+
+        ranges.sort_unstable();
+
+        RangeSet::from_sorted(ranges)
+    }
+}
+
+#[cfg(test)]
+fn ranges_test(set: &RangeSet) -> Vec<(u64, u64)> {
+    set.ranges().map(RangeInclusive::into_inner).collect()
+}
+
+#[test]
+fn check_insert() {
+    let mut set = RangeSet::new();
+
+    set.insert(0..=2);
+    set.insert(3..=4);
+
+    assert_eq!(ranges_test(&set), [(0, 4)]);
+
+    set.insert(10..=12);
+    set.insert(10..=12);
+
+    assert_eq!(ranges_test(&set), [(0, 4), (10, 12)]);
+
+    set.insert(6..=8);
+
+    assert_eq!(ranges_test(&set), [(0, 4), (6, 8), (10, 12)]);
+
+    set.insert(5..=7);
+
+    assert_eq!(ranges_test(&set), [(0, 8), (10, 12)]);
+
+    set.insert(2..=9);
+
+    assert_eq!(ranges_test(&set), [(0, 12)]);
+
+    set.insert(15..=20);
+    set.insert(10..=14);
+
+    assert_eq!(ranges_test(&set), [(0, 20)]);
+
+    #[allow(clippy::reversed_empty_ranges)]
+    set.insert(30..=25);
+
+    assert_eq!(ranges_test(&set), [(0, 20)]);
+}
+
+#[test]
+fn check_from_iter() {
+    let set: RangeSet = [16..=20, 3..=5, 12..=18, 10..=14].into_iter().collect();
+
+    assert_eq!(ranges_test(&set), [(3, 5), (10, 20)]);
+    assert_eq!(set.len(), 14);
+    assert_eq!(set.gaps().collect::<Vec<_>>(), [6..=9]);
+
+    for (value, expected) in [
+        (1, false),
+        (5, true),
+        (8, false),
+        (11, true),
+        (17, true),
+        (32, false),
+    ] {
+        assert_eq!(set.contains(value), expected, "{value}");
+    }
+}
+
+#[test]
+fn check_u64_bounds() {
+    let mut set: RangeSet = [u64::MAX - 1..=u64::MAX, 0..=0].into_iter().collect();
+
+    assert_eq!(set.len(), 3);
+    assert!(set.contains(u64::MAX));
+
+    set.insert(u64::MAX..=u64::MAX);
+    set.insert(u64::MAX - 5..=u64::MAX - 2);
+
+    assert_eq!(ranges_test(&set), [(0, 0), (u64::MAX - 5, u64::MAX)]);
+    assert_eq!(ranges_test(&set.complement()), [(1, u64::MAX - 6)]);
+
+    let full: RangeSet = [0..=u64::MAX, 5..=7].into_iter().collect();
+
+    assert_eq!(full.len(), 1 << 64);
+    assert!(full.complement().is_empty());
+    assert_eq!(RangeSet::new().complement(), full);
+}
+
+#[test]
+fn check_set_algebra() {
+    let a: RangeSet = [0..=10, 20..=30].into_iter().collect();
+    let b: RangeSet = [5..=22, 28..=40].into_iter().collect();
+
+    assert_eq!(ranges_test(&a.union(&b)), [(0, 40)]);
+    assert_eq!(
+        ranges_test(&a.intersection(&b)),
+        [(5, 10), (20, 22), (28, 30)]
+    );
+    assert_eq!(ranges_test(&a.difference(&b)), [(0, 4), (23, 27)]);
+    assert_eq!(ranges_test(&b.difference(&a)), [(11, 19), (31, 40)]);
+    assert_eq!(ranges_test(&a.complement()), [(11, 19), (31, u64::MAX)]);
+}
@@ -0,0 +1,301 @@
+use crate::cpu::CpuTime;
+use crate::memory::{format_bytes, MemoryStats};
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format {s:?}, expected text or json")),
+        }
+    }
+}
+
+/// Result of a day run.
+#[derive(Clone, Debug)]
+pub struct DayReport {
+    pub day_number: u32,
+    pub part1: String,
+    pub part2: String,
+    pub duration: Duration,
+    /// Phases marked by the day with [`crate::timing::lap`].
+    pub phases: Vec<(&'static str, Duration)>,
+    pub cpu: Option<CpuTime>,
+    pub memory: Option<MemoryStats>,
+}
+
+impl DayReport {
+    pub fn text(&self) -> String {
+        let DayReport {
+            day_number,
+            part1,
+            part2,
+            duration,
+            ..
+        } = self;
+        let mut line =
+            format!("day{day_number:0>2}: part1: {part1:20} part2: {part2:20} in {duration:?}");
+
+        if let Some(cpu) = self.cpu {
+            let _ = write!(
+                line,
+                " cpu {:?} user {:?} sys (x{:.2})",
+                cpu.user,
+                cpu.system,
+                cpu.ratio(*duration)
+            );
+        }
+        if let Some(memory) = self.memory {
+            let _ = write!(
+                line,
+                " peak {}, {} allocations ({})",
+                format_bytes(memory.peak),
+                memory.allocations,
+                format_bytes(memory.bytes)
+            );
+        }
+
+        line
+    }
+
+    pub fn json(&self) -> String {
+        let mut json = format!(
+            "{{\"day\":{},\"part1\":{},\"part2\":{},\"duration_ns\":{}",
+            self.day_number,
+            json_string(&self.part1),
+            json_string(&self.part2),
+            self.duration.as_nanos()
+        );
+
+        if !self.phases.is_empty() {
+            let phases: Vec<String> = self
+                .phases
+                .iter()
+                .map(|(name, d)| format!("{}:{}", json_string(name), d.as_nanos()))
+                .collect();
+
+            let _ = write!(json, ",\"phases_ns\":{{{}}}", phases.join(","));
+        }
+        if let Some(cpu) = self.cpu {
+            let _ = write!(
+                json,
+                ",\"user_ns\":{},\"system_ns\":{},\"cpu_ratio\":{:.3}",
+                cpu.user.as_nanos(),
+                cpu.system.as_nanos(),
+                cpu.ratio(self.duration)
+            );
+        }
+        if let Some(memory) = self.memory {
+            let _ = write!(
+                json,
+                ",\"peak_bytes\":{},\"allocations\":{},\"allocated_bytes\":{}",
+                memory.peak, memory.allocations, memory.bytes
+            );
+        }
+
+        json.push('}');
+        json
+    }
+}
+
+/// All the days of a run.
+pub struct Report {
+    pub days: Vec<DayReport>,
+    pub duration: Duration,
+    /// Stopped by a Ctrl-C before the last day.
+    pub interrupted: bool,
+}
+
+impl Report {
+    pub fn json(&self) -> String {
+        let days: Vec<String> = self.days.iter().map(DayReport::json).collect();
+
+        format!(
+            "{{\"days\":[{}],\"duration_ns\":{}{}}}",
+            days.join(","),
+            self.duration.as_nanos(),
+            if self.interrupted {
+                ",\"interrupted\":true"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut json = String::with_capacity(s.len() + 2);
+
+    json.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            c if c.is_control() => {
+                let _ = write!(json, "\\u{:04x}", c as u32);
+            }
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+
+    json
+}
+
+/// Org table with aligned columns.
+pub fn org_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let line = |cells: &mut dyn Iterator<Item = &str>| {
+        let cells: Vec<String> = cells
+            .zip(widths.iter())
+            .map(|(cell, &width)| {
+                let padding = width - cell.chars().count();
+
+                format!(" {cell}{} ", " ".repeat(padding))
+            })
+            .collect();
+
+        format!("|{}|\n", cells.join("|"))
+    };
+    let separator: Vec<String> = widths.iter().map(|&w| "-".repeat(w + 2)).collect();
+    let mut table = line(&mut headers.iter().copied());
+
+    table += &format!("|{}|\n", separator.join("+"));
+    for row in rows {
+        table += &line(&mut row.iter().map(String::as_str));
+    }
+
+    table
+}
+
+pub const SECTION_BEGIN: &str = "# BEGIN GENERATED RESULTS";
+pub const SECTION_END: &str = "# END GENERATED RESULTS";
+
+/// Replaces the lines between the section markers, the markers are
+/// added at the end of the text if missing.
+pub fn replace_section(text: &str, content: &str) -> String {
+    let begin = text.find(SECTION_BEGIN).map(|b| b + SECTION_BEGIN.len());
+    let end = begin.and_then(|b| text[b..].find(SECTION_END).map(|e| b + e));
+
+    match (begin, end) {
+        (Some(begin), Some(end)) => format!("{}\n{content}{}", &text[..begin], &text[end..]),
+        _ => {
+            let separator = if text.is_empty() || text.ends_with('\n') {
+                ""
+            } else {
+                "\n"
+            };
+
+            format!("{text}{separator}\n* Results\n{SECTION_BEGIN}\n{content}{SECTION_END}\n")
+        }
+    }
+}
+
+#[test]
+fn check_json() {
+    let report = DayReport {
+        day_number: 3,
+        part1: "42".to_string(),
+        part2: "a \"b\"\n".to_string(),
+        duration: Duration::from_micros(5),
+        phases: vec![("parse", Duration::from_micros(1))],
+        cpu: Some(CpuTime {
+            user: Duration::from_micros(8),
+            system: Duration::from_micros(2),
+        }),
+        memory: Some(MemoryStats {
+            peak: 10,
+            allocations: 2,
+            bytes: 20,
+        }),
+    };
+
+    assert_eq!(
+        report.json(),
+        r#"{"day":3,"part1":"42","part2":"a \"b\"\n","duration_ns":5000,"phases_ns":{"parse":1000},"user_ns":8000,"system_ns":2000,"cpu_ratio":2.000,"peak_bytes":10,"allocations":2,"allocated_bytes":20}"#
+    );
+    assert_eq!(
+        Report {
+            days: vec![],
+            duration: Duration::from_nanos(7),
+            interrupted: false,
+        }
+        .json(),
+        r#"{"days":[],"duration_ns":7}"#
+    );
+    assert_eq!(
+        Report {
+            days: vec![],
+            duration: Duration::from_nanos(7),
+            interrupted: true,
+        }
+        .json(),
+        r#"{"days":[],"duration_ns":7,"interrupted":true}"#
+    );
+}
+
+#[test]
+fn check_org_table() {
+    assert_eq!(
+        org_table(
+            &["Day", "Title"],
+            &[vec!["01".to_string(), "Secret Entrance".to_string()]]
+        ),
+        "| Day | Title           |
+|-----+-----------------|
+| 01  | Secret Entrance |
+"
+    );
+}
+
+#[test]
+fn check_replace_section() {
+    let text = "* Title
+text
+# BEGIN GENERATED RESULTS
+old
+# END GENERATED RESULTS
+after
+";
+
+    assert_eq!(
+        replace_section(text, "new\n"),
+        "* Title
+text
+# BEGIN GENERATED RESULTS
+new
+# END GENERATED RESULTS
+after
+"
+    );
+    assert_eq!(
+        replace_section("* Title", "new\n"),
+        "* Title
+
+* Results
+# BEGIN GENERATED RESULTS
+new
+# END GENERATED RESULTS
+"
+    );
+}
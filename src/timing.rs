@@ -0,0 +1,77 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+static RECORDING: AtomicBool = AtomicBool::new(false);
+static LAPS: Mutex<Vec<(&'static str, Instant)>> = Mutex::new(vec![]);
+
+/// Marks the end of a phase of the running day, like "parse" or
+/// "part1". Does nothing unless the phases are recorded.
+pub fn lap(phase: &'static str) {
+    if RECORDING.load(Ordering::Relaxed) {
+        LAPS.lock().unwrap().push((phase, Instant::now()));
+    }
+}
+
+/// Records the laps until `stop` is called.
+pub(crate) struct Recorder {
+    start: Instant,
+}
+
+impl Recorder {
+    /// The first phase begins at `start`.
+    pub(crate) fn start(start: Instant) -> Self {
+        LAPS.lock().unwrap().clear();
+        RECORDING.store(true, Ordering::Relaxed);
+
+        Recorder { start }
+    }
+
+    /// Duration of every phase, the time after the last lap being in
+    /// a "solve" phase. Empty if the day has no laps.
+    pub(crate) fn stop(self, end: Instant) -> Vec<(&'static str, Duration)> {
+        RECORDING.store(false, Ordering::Relaxed);
+
+        let laps = std::mem::take(&mut *LAPS.lock().unwrap());
+        let mut previous = self.start;
+        let mut phases: Vec<(&'static str, Duration)> = laps
+            .into_iter()
+            .map(|(phase, instant)| {
+                let duration = instant - previous;
+
+                previous = instant;
+                (phase, duration)
+            })
+            .collect();
+
+        if !phases.is_empty() && end > previous {
+            phases.push(("solve", end - previous));
+        }
+
+        phases
+    }
+}
+
+#[test]
+fn check_laps() {
+    lap("ignored");
+
+    let start = Instant::now();
+    let recorder = Recorder::start(start);
+
+    lap("parse");
+    lap("part1");
+
+    let end = Instant::now();
+    let phases = recorder.stop(end);
+    let names: Vec<&str> = phases.iter().map(|&(name, _)| name).collect();
+
+    assert_eq!(names[..2], ["parse", "part1"]);
+    assert_eq!(
+        phases.iter().map(|&(_, d)| d).sum::<Duration>(),
+        end - start
+    );
+    assert!(Recorder::start(Instant::now())
+        .stop(Instant::now())
+        .is_empty());
+}
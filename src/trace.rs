@@ -0,0 +1,111 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicU32, AtomicU8, Ordering};
+use std::sync::Mutex;
+
+/// Verbosity of an event, each level includes the ones before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info = 1,
+    Debug = 2,
+    Trace = 3,
+}
+
+impl Level {
+    /// Level given by the number of -v.
+    pub fn from_verbosity(count: u8) -> Self {
+        match count {
+            0 | 1 => Level::Info,
+            2 => Level::Debug,
+            _ => Level::Trace,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+struct Config {
+    level: Option<Level>,
+    /// Traced days, all of them if empty.
+    days: Vec<u32>,
+    /// Standard error if None.
+    output: Option<File>,
+}
+
+static CONFIG: Mutex<Config> = Mutex::new(Config {
+    level: None,
+    days: Vec::new(),
+    output: None,
+});
+/// Level of the running day, 0 when it is not traced. Only the
+/// commands solving one day at a time enter the days.
+static ACTIVE: AtomicU8 = AtomicU8::new(0);
+static DAY: AtomicU32 = AtomicU32::new(0);
+
+/// Enables the events up to `level` for some days, all of them if
+/// `days` is empty.
+pub fn init(level: Level, days: Vec<u32>, output: Option<File>) {
+    *CONFIG.lock().unwrap() = Config {
+        level: Some(level),
+        days,
+        output,
+    };
+}
+
+pub(crate) fn enter(day_number: u32) {
+    let config = CONFIG.lock().unwrap();
+    let level = config
+        .level
+        .filter(|_| config.days.is_empty() || config.days.contains(&day_number));
+
+    DAY.store(day_number, Ordering::Relaxed);
+    ACTIVE.store(level.map_or(0, |l| l as u8), Ordering::Relaxed);
+}
+
+pub(crate) fn leave() {
+    ACTIVE.store(0, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level as u8 <= ACTIVE.load(Ordering::Relaxed)
+}
+
+/// Writes an event, use the [`trace!`](crate::trace!) macro instead.
+#[doc(hidden)]
+pub fn event(level: Level, args: fmt::Arguments) {
+    let mut config = CONFIG.lock().unwrap();
+    let day_number = DAY.load(Ordering::Relaxed);
+    let line = format!("[day{day_number:0>2} {}] {args}\n", level.name());
+    let _ = match config.output.as_mut() {
+        Some(file) => file.write_all(line.as_bytes()),
+        None => io::stderr().write_all(line.as_bytes()),
+    };
+}
+
+/// Emits an event of the running day, like
+/// `trace!(Info, "{n} rolls removed")`. The arguments are not even
+/// formatted when the level is disabled.
+#[macro_export]
+macro_rules! trace {
+    ($level:ident, $($arg:tt)+) => {
+        if $crate::trace::enabled($crate::trace::Level::$level) {
+            $crate::trace::event($crate::trace::Level::$level, format_args!($($arg)+));
+        }
+    };
+}
+
+#[test]
+fn check_levels() {
+    assert!(!enabled(Level::Info));
+    assert_eq!(Level::from_verbosity(0), Level::Info);
+    assert_eq!(Level::from_verbosity(2), Level::Debug);
+    assert_eq!(Level::from_verbosity(7), Level::Trace);
+    assert!(Level::Info < Level::Trace);
+}
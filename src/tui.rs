@@ -0,0 +1,417 @@
+use crate::history::{self, Entry};
+use crate::puzzle::Puzzle;
+use crate::report::org_table;
+use crate::{
+    catch_resolve, example_checks, input_path, median_duration, read_input, read_puzzle_markdown,
+    select_days, without_panic_messages, Day,
+};
+use std::io::{self, Read, Write};
+use std::panic;
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use std::time::{Duration, Instant};
+
+const HEADERS: [&str; 8] = [
+    "Day", "Title", "Input", "Part 1", "Part 2", "Verify", "Time", "Example",
+];
+const HELP: &str = "↑/↓ select  r run  e example  m puzzle  b bench  q quit";
+
+/// What the dashboard knows about a day.
+struct Row {
+    day: &'static Day,
+    day_number: u32,
+    title: Option<String>,
+    /// Accepted answers, from the cached puzzle page.
+    known: Vec<String>,
+    answers: Option<Result<(String, String), String>>,
+    /// Of the last run, or the last recorded one.
+    duration: Option<Duration>,
+    example: Option<bool>,
+}
+
+/// Status of the last answers against the accepted ones.
+fn verify_status(
+    known: &[String],
+    answers: Option<&Result<(String, String), String>>,
+) -> &'static str {
+    match answers {
+        None => "-",
+        Some(Err(_)) => "panic",
+        Some(Ok(_)) if known.is_empty() => "unknown",
+        Some(Ok((part1, part2))) => {
+            if [part1, part2].into_iter().zip(known).all(|(a, k)| a == k) {
+                "ok"
+            } else {
+                "FAIL"
+            }
+        }
+    }
+}
+
+impl Row {
+    fn new(day: &'static Day, entries: &[Entry]) -> Self {
+        let day_number = day.parse_number();
+        let puzzle = read_puzzle_markdown(None, day_number)
+            .map(|markdown| Puzzle::from_markdown(&markdown))
+            .unwrap_or_default();
+
+        Row {
+            day,
+            day_number,
+            title: puzzle.title,
+            known: puzzle.answers,
+            answers: None,
+            duration: entries
+                .iter()
+                .rfind(|e| e.day_number == day_number)
+                .map(|e| e.duration),
+            example: None,
+        }
+    }
+
+    fn cells(&self) -> Vec<String> {
+        let (part1, part2) = match &self.answers {
+            Some(Ok((part1, part2))) => (part1.clone(), part2.clone()),
+            _ => ("-".to_string(), "-".to_string()),
+        };
+
+        vec![
+            format!("{:0>2}", self.day_number),
+            self.title.clone().unwrap_or_else(|| "-".to_string()),
+            if input_path(self.day_number).exists() {
+                "cached"
+            } else {
+                "missing"
+            }
+            .to_string(),
+            part1,
+            part2,
+            verify_status(&self.known, self.answers.as_ref()).to_string(),
+            self.duration
+                .map_or("-".to_string(), |d| format!("{d:.2?}")),
+            match self.example {
+                None => "-",
+                Some(true) => "ok",
+                Some(false) => "FAIL",
+            }
+            .to_string(),
+        ]
+    }
+
+    /// Solves the input, returns the status message.
+    fn solve(&mut self, session: Option<&str>) -> Result<String, String> {
+        if session.is_none() && !input_path(self.day_number).exists() {
+            return Err(format!(
+                "day{:0>2} has no cached input, a session is needed to download it",
+                self.day_number
+            ));
+        }
+
+        let input = read_input(session, self.day_number)
+            .map_err(|e| format!("unable to read the input: {e}"))?;
+        let start = Instant::now();
+        let answers = without_panic_messages(|| catch_resolve(self.day.resolve, &input));
+
+        self.duration = Some(start.elapsed());
+        self.answers = Some(answers.clone());
+
+        // The stars earned since the start are only known with a session.
+        if self.known.len() < 2 {
+            if let Ok(markdown) = read_puzzle_markdown(session, self.day_number) {
+                self.known = Puzzle::from_markdown(&markdown).answers;
+            }
+        }
+
+        answers
+            .map(|_| format!("day{:0>2} solved in {:?}", self.day_number, start.elapsed()))
+            .map_err(|message| format!("day{:0>2} panicked: {message}", self.day_number))
+    }
+
+    fn bench(&mut self, session: Option<&str>) -> Result<String, String> {
+        const RUNS: usize = 5;
+
+        self.solve(session)?;
+
+        let input = read_input(session, self.day_number).map_err(|e| e.to_string())?;
+        let resolve = self.day.resolve;
+        let median = without_panic_messages(|| {
+            panic::catch_unwind(|| median_duration(resolve, &input, RUNS))
+        })
+        .map_err(|_| "panicked while benching".to_string())?;
+
+        self.duration = Some(median);
+
+        Ok(format!(
+            "day{:0>2} median of {RUNS} runs {median:?}",
+            self.day_number
+        ))
+    }
+
+    fn run_example(&mut self) -> Result<String, String> {
+        let result = example_checks(self.day_number);
+
+        self.example = Some(result.is_ok());
+
+        match result {
+            Ok(()) => Ok(format!("day{:0>2} examples ok", self.day_number)),
+            Err(output) => Err(output
+                .lines()
+                .find(|line| line.contains("panicked") || line.starts_with("error"))
+                .unwrap_or("examples failed")
+                .to_string()),
+        }
+    }
+}
+
+/// Raw mode on the alternate screen, restored when dropped.
+struct Terminal {
+    #[cfg(unix)]
+    saved: libc::termios,
+}
+
+impl Terminal {
+    #[cfg(unix)]
+    fn enter() -> io::Result<Self> {
+        // SAFETY: termios is a plain C struct, filled by tcgetattr.
+        let mut saved: libc::termios = unsafe { std::mem::zeroed() };
+
+        // SAFETY: the pointer is valid for the call.
+        if unsafe { libc::tcgetattr(libc::STDIN_FILENO, &mut saved) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        let mut raw = saved;
+
+        // Ctrl-C comes as a key, quitting through the restoring drop.
+        raw.c_lflag &= !(libc::ICANON | libc::ECHO | libc::ISIG);
+        raw.c_cc[libc::VMIN] = 1;
+        raw.c_cc[libc::VTIME] = 0;
+
+        // SAFETY: same as above.
+        if unsafe { libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &raw) } != 0 {
+            return Err(io::Error::last_os_error());
+        }
+
+        print!("\x1b[?1049h\x1b[?25l");
+        io::stdout().flush()?;
+
+        Ok(Terminal { saved })
+    }
+
+    #[cfg(not(unix))]
+    fn enter() -> io::Result<Self> {
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "the dashboard needs a unix terminal",
+        ))
+    }
+
+    #[cfg(unix)]
+    fn height() -> usize {
+        // SAFETY: winsize is a plain C struct, filled by the ioctl.
+        let mut size: libc::winsize = unsafe { std::mem::zeroed() };
+
+        // SAFETY: the pointer is valid for the call.
+        match unsafe { libc::ioctl(libc::STDOUT_FILENO, libc::TIOCGWINSZ, &mut size) } {
+            0 if size.ws_row > 0 => size.ws_row as usize,
+            _ => 24,
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn height() -> usize {
+        24
+    }
+
+    /// Whether more bytes are ready, telling an escape sequence from a
+    /// lone escape key.
+    #[cfg(unix)]
+    fn pending() -> bool {
+        let mut poll = libc::pollfd {
+            fd: libc::STDIN_FILENO,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+
+        // SAFETY: a single valid pollfd is given.
+        unsafe { libc::poll(&mut poll, 1, 30) > 0 }
+    }
+
+    #[cfg(not(unix))]
+    fn pending() -> bool {
+        false
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        print!("\x1b[?25h\x1b[?1049l");
+        let _ = io::stdout().flush();
+
+        // SAFETY: restores the attributes read by tcgetattr.
+        #[cfg(unix)]
+        unsafe {
+            libc::tcsetattr(libc::STDIN_FILENO, libc::TCSANOW, &self.saved);
+        }
+    }
+}
+
+/// Byte of Ctrl-C, without ISIG.
+const CTRL_C: u8 = 0x03;
+
+enum Key {
+    Up,
+    Down,
+    Escape,
+    Char(u8),
+}
+
+fn read_key() -> io::Result<Key> {
+    let mut stdin = io::stdin().lock();
+    let mut byte = [0u8];
+
+    stdin.read_exact(&mut byte)?;
+    if byte[0] != 0x1b {
+        return Ok(Key::Char(byte[0]));
+    }
+    if !Terminal::pending() {
+        return Ok(Key::Escape);
+    }
+
+    let mut sequence = [0u8; 2];
+
+    stdin.read_exact(&mut sequence)?;
+    Ok(match sequence {
+        [b'[', b'A'] => Key::Up,
+        [b'[', b'B'] => Key::Down,
+        _ => Key::Char(0),
+    })
+}
+
+fn show(screen: &str) -> io::Result<()> {
+    let mut stdout = io::stdout().lock();
+
+    write!(stdout, "\x1b[H\x1b[2J{screen}")?;
+    stdout.flush()
+}
+
+fn draw(rows: &[Row], selected: usize, status: &str) -> io::Result<()> {
+    let cells: Vec<Vec<String>> = rows.iter().map(Row::cells).collect();
+    let mut screen = "Advent of Code 2025\n\n".to_string();
+
+    for (i, line) in org_table(&HEADERS, &cells).lines().enumerate() {
+        if i == selected + 2 {
+            screen += &format!("\x1b[7m{line}\x1b[0m\n");
+        } else {
+            screen += &format!("{line}\n");
+        }
+    }
+
+    show(&format!("{screen}\n{status}\n{HELP}"))
+}
+
+/// Scrolls through a text until q or escape.
+fn page(text: &str) -> io::Result<()> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut top = 0;
+
+    loop {
+        let height = Terminal::height().saturating_sub(1).max(1);
+        let end = (top + height).min(lines.len());
+
+        show(&format!(
+            "{}\n\x1b[7mlines {}-{end} of {}  ↑/↓ scroll  space next page  q back\x1b[0m",
+            lines[top..end].join("\n"),
+            top + 1,
+            lines.len()
+        ))?;
+
+        let last = lines.len().saturating_sub(height);
+
+        top = match read_key()? {
+            Key::Up | Key::Char(b'k') => top.saturating_sub(1),
+            Key::Down | Key::Char(b'j') => (top + 1).min(last),
+            Key::Char(b' ') => (top + height).min(last),
+            Key::Escape | Key::Char(b'q' | CTRL_C) => return Ok(()),
+            _ => top,
+        };
+    }
+}
+
+/// Interactive table of the days. Everything is local, the inputs are
+/// only downloaded when a day without one is run with a session.
+pub fn run(session: Option<&str>) -> io::Result<()> {
+    let entries = history::load(Path::new(history::HISTORY_FILE)).unwrap_or_default();
+    let mut rows: Vec<Row> = select_days(&[])
+        .into_iter()
+        .map(|day| Row::new(day, &entries))
+        .collect();
+    let _terminal = Terminal::enter()?;
+
+    // The status line tells about the downloads instead.
+    crate::QUIET.store(true, Ordering::Relaxed);
+    let mut selected = 0;
+    let mut status = String::new();
+
+    loop {
+        draw(&rows, selected, &status)?;
+
+        let key = read_key()?;
+        let downloading = session.is_some() && !input_path(rows[selected].day_number).exists();
+        let busy = match key {
+            Key::Char(b'r' | b'b') if downloading => Some("downloading the input..."),
+            Key::Char(b'r') => Some("solving..."),
+            Key::Char(b'b') => Some("benching..."),
+            Key::Char(b'e') => Some("running the examples..."),
+            _ => None,
+        };
+
+        if let Some(message) = busy {
+            draw(&rows, selected, message)?;
+        }
+
+        let row = &mut rows[selected];
+        let result = match key {
+            Key::Up | Key::Char(b'k') => {
+                selected = selected.saturating_sub(1);
+                continue;
+            }
+            Key::Down | Key::Char(b'j') => {
+                selected = (selected + 1).min(rows.len() - 1);
+                continue;
+            }
+            Key::Char(b'r') => row.solve(session),
+            Key::Char(b'b') => row.bench(session),
+            Key::Char(b'e') => row.run_example(),
+            Key::Char(b'm') => match read_puzzle_markdown(None, row.day_number) {
+                Ok(markdown) => page(&markdown)
+                    .map(|_| String::new())
+                    .map_err(|e| e.to_string()),
+                Err(_) => Err(format!(
+                    "no cached puzzle in ./inputs/{:0>2}.md",
+                    row.day_number
+                )),
+            },
+            Key::Escape | Key::Char(b'q' | CTRL_C) => return Ok(()),
+            _ => continue,
+        };
+
+        status = result.unwrap_or_else(|message| message);
+    }
+}
+
+#[test]
+fn check_verify_status() {
+    let known = ["1".to_string(), "2".to_string()];
+    let answers = |p1: &str, p2: &str| Ok((p1.to_string(), p2.to_string()));
+
+    assert_eq!(verify_status(&known, None), "-");
+    assert_eq!(verify_status(&known, Some(&answers("1", "2"))), "ok");
+    assert_eq!(verify_status(&known[..1], Some(&answers("1", "3"))), "ok");
+    assert_eq!(verify_status(&known, Some(&answers("1", "3"))), "FAIL");
+    assert_eq!(verify_status(&[], Some(&answers("1", "3"))), "unknown");
+    assert_eq!(
+        verify_status(&known, Some(&Err("oops".to_string()))),
+        "panic"
+    );
+}
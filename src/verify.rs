@@ -0,0 +1,319 @@
+use crate::html::escape;
+use std::fmt::Write;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How the verify results are printed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Style {
+    #[default]
+    Text,
+    Junit,
+    Tap,
+}
+
+impl FromStr for Style {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Style::Text),
+            "junit" => Ok(Style::Junit),
+            "tap" => Ok(Style::Tap),
+            _ => Err(format!("unknown report {s:?}, expected text, junit or tap")),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    Timeout(Duration),
+    Panic(String),
+    /// Different answers in repeated runs.
+    Unstable(Vec<String>),
+    /// The puzzle page has no answer for this part.
+    Skipped,
+}
+
+/// Check of one part of a day against its known answer.
+#[derive(Clone, Debug)]
+pub struct Case {
+    pub day_number: u32,
+    pub part: usize,
+    pub outcome: Outcome,
+    pub duration: Duration,
+}
+
+impl Case {
+    fn name(&self) -> String {
+        format!("day{:0>2} part{}", self.day_number, self.part)
+    }
+
+    pub fn failed(&self) -> bool {
+        !matches!(self.outcome, Outcome::Pass | Outcome::Skipped)
+    }
+}
+
+/// Checks of a day from the outcomes of its runs. A part whose answer
+/// changes between the runs is unstable, whatever the known answer.
+pub fn cases(
+    day_number: u32,
+    known: &[String],
+    runs: &[Result<(String, String), Outcome>],
+    duration: Duration,
+) -> Vec<Case> {
+    (1..=2)
+        .map(|part| {
+            let mut answers = vec![];
+            let mut error = None;
+
+            for run in runs {
+                match run {
+                    Ok((part1, part2)) => {
+                        let answer = if part == 1 { part1 } else { part2 };
+
+                        if !answers.contains(answer) {
+                            answers.push(answer.clone());
+                        }
+                    }
+                    Err(outcome) => {
+                        error.get_or_insert(outcome);
+                    }
+                }
+            }
+
+            // A panic or a timeout fails even without a known answer.
+            let outcome = match (error, known.get(part - 1)) {
+                (Some(outcome), _) => outcome.clone(),
+                _ if answers.len() > 1 => Outcome::Unstable(answers),
+                (None, None) => Outcome::Skipped,
+                (None, Some(expected)) if answers[0] == *expected => Outcome::Pass,
+                (None, Some(expected)) => Outcome::Fail {
+                    expected: expected.clone(),
+                    actual: answers.swap_remove(0),
+                },
+            };
+
+            Case {
+                day_number,
+                part,
+                outcome,
+                duration,
+            }
+        })
+        .collect()
+}
+
+pub fn text(cases: &[Case]) -> String {
+    let mut text = String::new();
+
+    for case in cases {
+        let status = match &case.outcome {
+            Outcome::Pass => "ok".to_string(),
+            Outcome::Fail { expected, actual } => {
+                format!("FAILED expected {expected} got {actual}")
+            }
+            Outcome::Timeout(limit) => format!("TIMEOUT after {limit:?}"),
+            Outcome::Panic(message) => format!("PANICKED {message}"),
+            Outcome::Unstable(answers) => format!("UNSTABLE answers {}", answers.join(", ")),
+            Outcome::Skipped => "skipped, no known answer".to_string(),
+        };
+
+        let _ = writeln!(text, "{}: {status}", case.name());
+    }
+
+    let failed = cases.iter().filter(|c| c.failed()).count();
+    let skipped = cases
+        .iter()
+        .filter(|c| c.outcome == Outcome::Skipped)
+        .count();
+
+    let _ = writeln!(
+        text,
+        "{} passed, {failed} failed, {skipped} skipped",
+        cases.len() - failed - skipped
+    );
+
+    text
+}
+
+pub fn junit(cases: &[Case]) -> String {
+    let count = |f: fn(&Outcome) -> bool| cases.iter().filter(|c| f(&c.outcome)).count();
+    let failures = count(|o| matches!(o, Outcome::Fail { .. } | Outcome::Unstable(_)));
+    let errors = count(|o| matches!(o, Outcome::Timeout(_) | Outcome::Panic(_)));
+    let skipped = count(|o| *o == Outcome::Skipped);
+    let time: f64 = cases.iter().map(|c| c.duration.as_secs_f64()).sum();
+    let attributes = format!(
+        "name=\"advent_2025\" tests=\"{}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\" time=\"{time:.6}\"",
+        cases.len()
+    );
+    let mut xml = format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites {attributes}>\n<testsuite {attributes}>\n"
+    );
+
+    for case in cases {
+        let _ = write!(
+            xml,
+            "<testcase classname=\"day{:0>2}\" name=\"part{}\" time=\"{:.6}\"",
+            case.day_number,
+            case.part,
+            case.duration.as_secs_f64()
+        );
+
+        let _ = match &case.outcome {
+            Outcome::Pass => writeln!(xml, "/>"),
+            Outcome::Fail { expected, actual } => writeln!(
+                xml,
+                "><failure message=\"expected {0} got {1}\">expected: {0}\nactual: {1}</failure></testcase>",
+                escape(expected),
+                escape(actual)
+            ),
+            Outcome::Timeout(limit) => writeln!(
+                xml,
+                "><error type=\"timeout\" message=\"timed out after {limit:?}\"/></testcase>"
+            ),
+            Outcome::Panic(message) => writeln!(
+                xml,
+                "><error type=\"panic\" message=\"{0}\">{0}</error></testcase>",
+                escape(message)
+            ),
+            Outcome::Unstable(answers) => writeln!(
+                xml,
+                "><failure message=\"unstable answers\">{}</failure></testcase>",
+                escape(&answers.join("\n"))
+            ),
+            Outcome::Skipped => writeln!(
+                xml,
+                "><skipped message=\"no known answer\"/></testcase>"
+            ),
+        };
+    }
+
+    xml + "</testsuite>\n</testsuites>\n"
+}
+
+fn yaml_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''").replace('\n', " "))
+}
+
+pub fn tap(cases: &[Case]) -> String {
+    let mut tap = format!("TAP version 13\n1..{}\n", cases.len());
+
+    for (i, case) in cases.iter().enumerate() {
+        let number = i + 1;
+        let name = case.name();
+
+        let _ = match &case.outcome {
+            Outcome::Pass => writeln!(tap, "ok {number} - {name}"),
+            Outcome::Skipped => writeln!(tap, "ok {number} - {name} # SKIP no known answer"),
+            Outcome::Fail { expected, actual } => writeln!(
+                tap,
+                "not ok {number} - {name}\n  ---\n  message: wrong answer\n  expected: {}\n  got: {}\n  ...",
+                yaml_string(expected),
+                yaml_string(actual)
+            ),
+            Outcome::Timeout(limit) => writeln!(
+                tap,
+                "not ok {number} - {name}\n  ---\n  message: timed out after {limit:?}\n  severity: timeout\n  ..."
+            ),
+            Outcome::Unstable(answers) => {
+                let answers: Vec<String> = answers.iter().map(|a| yaml_string(a)).collect();
+
+                writeln!(
+                    tap,
+                    "not ok {number} - {name}\n  ---\n  message: unstable answers\n  got: [{}]\n  ...",
+                    answers.join(", ")
+                )
+            }
+            Outcome::Panic(message) => writeln!(
+                tap,
+                "not ok {number} - {name}\n  ---\n  message: {}\n  severity: panic\n  ...",
+                yaml_string(message)
+            ),
+        };
+    }
+
+    tap
+}
+
+#[cfg(test)]
+fn cases_test() -> Vec<Case> {
+    let known = ["1".to_string(), "2".to_string()];
+    let duration = Duration::from_millis(1);
+    let answers = |p1: &str, p2: &str| Ok((p1.to_string(), p2.to_string()));
+    let mut all = cases(1, &known, &[answers("1", "<3>")], duration);
+
+    all.extend(cases(
+        2,
+        &known[..1],
+        &[Err(Outcome::Panic("oops".to_string()))],
+        duration,
+    ));
+    all.extend(cases(
+        3,
+        &known,
+        &[Err(Outcome::Timeout(Duration::from_secs(2)))],
+        duration,
+    ));
+    all.extend(cases(
+        4,
+        &[],
+        &[answers("1", "2"), answers("1", "3"), answers("1", "2")],
+        duration,
+    ));
+
+    all
+}
+
+#[test]
+fn check_cases() {
+    let outcomes: Vec<Outcome> = cases_test().into_iter().map(|c| c.outcome).collect();
+
+    assert_eq!(
+        outcomes,
+        [
+            Outcome::Pass,
+            Outcome::Fail {
+                expected: "2".to_string(),
+                actual: "<3>".to_string()
+            },
+            Outcome::Panic("oops".to_string()),
+            Outcome::Panic("oops".to_string()),
+            Outcome::Timeout(Duration::from_secs(2)),
+            Outcome::Timeout(Duration::from_secs(2)),
+            Outcome::Skipped,
+            Outcome::Unstable(vec!["2".to_string(), "3".to_string()]),
+        ]
+    );
+    assert!(text(&cases_test()).ends_with("1 passed, 6 failed, 1 skipped\n"));
+}
+
+#[test]
+fn check_junit() {
+    let xml = junit(&cases_test());
+
+    assert!(xml.contains("tests=\"8\" failures=\"2\" errors=\"4\" skipped=\"1\""));
+    assert!(xml.contains(
+        "<testcase classname=\"day01\" name=\"part2\" time=\"0.001000\"><failure message=\"expected 2 got &lt;3&gt;\">"
+    ));
+    assert!(xml.contains("<error type=\"timeout\" message=\"timed out after 2s\"/>"));
+    assert!(xml.contains("<skipped message=\"no known answer\"/>"));
+}
+
+#[test]
+fn check_tap() {
+    let tap = tap(&cases_test());
+
+    assert!(tap.starts_with("TAP version 13\n1..8\nok 1 - day01 part1\nnot ok 2 - day01 part2\n"));
+    assert!(tap.contains("not ok 4 - day02 part2\n"));
+    assert!(tap.contains("ok 7 - day04 part1 # SKIP no known answer\n"));
+    assert!(tap.contains("  message: 'oops'\n  severity: panic\n"));
+    assert!(tap.contains(
+        "not ok 8 - day04 part2\n  ---\n  message: unstable answers\n  got: ['2', '3']\n"
+    ));
+}